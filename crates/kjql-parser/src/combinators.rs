@@ -1,9 +1,8 @@
 use winnow::{
-    PResult,
     Parser,
     ascii::{
         digit1,
-        multispace0,
+        multispace1,
     },
     combinator::{
         alt,
@@ -14,22 +13,42 @@ use winnow::{
         peek,
         preceded,
         repeat,
-        separated,
         separated_pair,
     },
-    error::ParserError,
+    error::{
+        ParserError,
+        StrContext,
+        StrContextValue,
+    },
     token::{
         any,
         literal,
+        take_till,
         take_until,
+        take_while,
     },
 };
 
-use crate::tokens::{
-    Index,
-    LensValue,
-    Range,
-    Token,
+use crate::{
+    errors::{
+        PResult,
+        SpanError,
+    },
+    tokens::{
+        Alignment,
+        BinaryOperator,
+        Index,
+        JsonType,
+        KeyRange,
+        Lens,
+        LensValue,
+        MergeStrategy,
+        Number,
+        Order,
+        Range,
+        Selection,
+        Token,
+    },
 };
 
 /// Colon.
@@ -44,6 +63,30 @@ static CURLY_BRACKET_CLOSE: char = '}';
 static DOUBLE_QUOTE: char = '"';
 /// Equal.
 static EQUAL: char = '=';
+/// Parenthesis open.
+static PARENTHESIS_OPEN: char = '(';
+/// Parenthesis close.
+static PARENTHESIS_CLOSE: char = ')';
+/// Dot.
+static DOT: char = '.';
+/// Plus.
+static PLUS: char = '+';
+/// Minus.
+static MINUS: char = '-';
+/// Asterisk.
+static ASTERISK: char = '*';
+/// Slash.
+static SLASH: char = '/';
+/// Percent.
+static PERCENT: char = '%';
+/// Ampersand.
+static AMPERSAND: char = '&';
+/// Vertical bar.
+static VERTICAL_BAR: char = '|';
+/// Angle bracket open.
+static ANGLE_BRACKET_OPEN: char = '<';
+/// Angle bracket close.
+static ANGLE_BRACKET_CLOSE: char = '>';
 /// Square brace open.
 static SQUARE_BRACKET_OPEN: char = '[';
 /// Square brace close.
@@ -60,59 +103,284 @@ static KEYS: &str = "@";
 static FLATTEN: &str = "..";
 /// Group separator.
 static GROUP_SEP: &str = ",";
+/// Exclusive key range separator.
+static EXCLUSIVE_RANGE_SEP: &str = "..";
 /// Pipe in operator.
 static PIPE_IN: &str = "|>";
 /// Pipe out operator
 static PIPE_OUT: &str = "<|";
 /// Truncate operator
 static TRUNCATE: &str = "!";
+/// Sort operator.
+static SORT: &str = "sort";
+/// Sort by operator.
+static SORT_BY: &str = "sort_by";
+/// Average operator.
+static AVERAGE: &str = "avg";
+/// Max operator.
+static MAX: &str = "max";
+/// Max by operator.
+static MAX_BY: &str = "max_by";
+/// Min operator.
+static MIN: &str = "min";
+/// Min by operator.
+static MIN_BY: &str = "min_by";
+/// Sum operator.
+static SUM: &str = "sum";
+/// Group by operator.
+static GROUP_BY: &str = "group_by";
+/// Unique operator.
+static UNIQUE: &str = "unique";
+/// Unique by operator.
+static UNIQUE_BY: &str = "unique_by";
+/// Split operator.
+static SPLIT: &str = "split";
+/// Join operator.
+static JOIN: &str = "join";
+/// Lower operator.
+static LOWER: &str = "lower";
+/// Upper operator.
+static UPPER: &str = "upper";
+/// Trim operator.
+static TRIM: &str = "trim";
+/// Replace operator.
+static REPLACE: &str = "replace";
+/// To string operator.
+static TO_STRING: &str = "tostring";
+/// To number operator.
+static TO_NUMBER: &str = "tonumber";
+/// To JSON operator.
+static TO_JSON: &str = "tojson";
+/// From JSON operator.
+static FROM_JSON: &str = "fromjson";
+/// Limit operator.
+static LIMIT: &str = "limit";
+/// Skip operator.
+static SKIP: &str = "skip";
+/// First operator.
+static FIRST: &str = "first";
+/// Last operator.
+static LAST: &str = "last";
+/// Zip operator.
+static ZIP: &str = "zip";
+/// Zip object operator.
+static ZIP_OBJECT: &str = "zip_object";
+/// Transpose operator.
+static TRANSPOSE: &str = "transpose";
+/// Padding suffix.
+static PAD: &str = "_pad";
+/// Merge operator.
+static MERGE: &str = "merge";
+/// Merge with concatenation operator.
+static MERGE_CONCAT: &str = "merge_concat";
+/// Has operator.
+static HAS: &str = "has";
+/// Exists lens value.
+static EXISTS: &str = "exists";
+/// Values operator.
+static VALUES: &str = "values";
+/// Descendants operator.
+static DESCENDANTS: &str = "descendants";
+/// Conditional start.
+static IF: &str = "if";
+/// Conditional consequent.
+static THEN: &str = "then";
+/// Conditional alternative.
+static ELSE: &str = "else";
+/// Conditional end.
+static END: &str = "end";
+/// Type operator.
+static TYPE: &str = "type";
+/// Descending order suffix.
+static DESCENDING: &str = "_desc";
+/// Line comment start.
+static LINE_COMMENT: &str = "#";
+/// Block comment start.
+static BLOCK_COMMENT_START: &str = "/*";
+/// Block comment end.
+static BLOCK_COMMENT_END: &str = "*/";
+
+/// A combinator which consumes whitespaces, `#` line comments and `/* */`
+/// block comments.
+pub(crate) fn parse_whitespace<'a, E>(input: &mut &'a str) -> winnow::PResult<(), E>
+where
+    E: ParserError<&'a str>,
+{
+    repeat(
+        0..,
+        alt((
+            multispace1.void(),
+            (LINE_COMMENT, take_till(0.., '\n')).void(),
+            (
+                BLOCK_COMMENT_START,
+                take_until(0.., BLOCK_COMMENT_END),
+                BLOCK_COMMENT_END,
+            )
+                .void(),
+        )),
+    )
+    .parse_next(input)
+}
 
 /// A combinator which takes an `inner` parser and produces a parser which also
-/// consumes both leading and trailing whitespaces, returning the output of `inner`.
+/// consumes both leading and trailing whitespaces and comments, returning the
+/// output of `inner`.
 pub(crate) fn trim<'a, F, O, E>(inner: F) -> impl Parser<&'a str, O, E>
 where
     E: ParserError<&'a str>,
     F: Parser<&'a str, O, E>,
 {
-    delimited(multispace0, inner, multispace0)
+    delimited(parse_whitespace, inner, parse_whitespace)
+}
+
+/// A combinator which parses the provided character, reporting it as expected
+/// on failure.
+fn expect<'a>(character: char) -> impl Parser<&'a str, char, SpanError> {
+    character.context(StrContext::Expected(StrContextValue::CharLiteral(
+        character,
+    )))
+}
+
+/// A combinator which parses the provided literal, reporting it as expected on
+/// failure.
+fn expect_literal<'a>(value: &'static str) -> impl Parser<&'a str, &'a str, SpanError> {
+    literal(value).context(StrContext::Expected(StrContextValue::StringLiteral(value)))
+}
+
+/// A combinator which parses a word made of identifier characters.
+fn parse_word<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    take_while(1.., |character: char| {
+        character.is_alphanumeric() || character == '_'
+    })
+    .parse_next(input)
+}
+
+/// A combinator which parses the provided keyword as a whole word, e.g.
+/// `first` but not the beginning of `firstly`.
+fn keyword<'a>(value: &'static str) -> impl Parser<&'a str, &'a str, SpanError> {
+    parse_word.verify(move |word: &str| word == value)
+}
+
+/// A combinator which parses the provided keyword, reporting it as expected
+/// on failure.
+fn expect_keyword<'a>(value: &'static str) -> impl Parser<&'a str, &'a str, SpanError> {
+    keyword(value).context(StrContext::Expected(StrContextValue::StringLiteral(value)))
+}
+
+/// A combinator which parses the character closing a list, reporting both the
+/// character and a comma as expected on failure.
+fn list_end<'a>(character: char) -> impl Parser<&'a str, char, SpanError> {
+    trim(expect(character).context(StrContext::Expected(StrContextValue::CharLiteral(COMMA))))
+}
+
+/// A combinator which parses at least `min` occurrences of `item` separated by
+/// commas.
+/// Unlike `separated`, a comma must be followed by an `item`, the failure being
+/// reported where the `item` was expected.
+fn parse_list<'a, O, F>(min: usize, mut item: F) -> impl Parser<&'a str, Vec<O>, SpanError>
+where
+    F: Parser<&'a str, O, SpanError>,
+{
+    move |input: &mut &'a str| {
+        let mut items = Vec::new();
+
+        loop {
+            items.push(item.parse_next(input)?);
+
+            if items.len() < min {
+                trim(expect(COMMA)).parse_next(input)?;
+            } else if opt(trim(expect(COMMA))).parse_next(input)?.is_none() {
+                return Ok(items);
+            }
+        }
+    }
 }
 
 /// A combinator which parses a stringified number as an `Index`.
 pub(crate) fn parse_number(input: &mut &str) -> PResult<Index> {
-    digit1.parse_to().parse_next(input)
+    digit1
+        .parse_to()
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a number",
+        )))
+        .parse_next(input)
+}
+
+/// A combinator which parses a negative number as an `Index` counted from the
+/// end.
+fn parse_negative_number(input: &mut &str) -> PResult<Index> {
+    preceded(trim(expect(MINUS)), parse_number)
+        .verify(|index: &Index| index.0 > 0)
+        .parse_next(input)
 }
 
 /// A combinator which parses a key surrounded by double quotes.
 pub(crate) fn parse_key<'a>(input: &mut &'a str) -> PResult<&'a str> {
     trim(delimited(
-        DOUBLE_QUOTE,
-        take_until(0.., r#"""#),
-        DOUBLE_QUOTE,
+        expect(DOUBLE_QUOTE),
+        take_till(0.., DOUBLE_QUOTE),
+        expect(DOUBLE_QUOTE),
     ))
     .parse_next(input)
 }
 
+/// A combinator which parses a number literal.
+/// Numbers with a fractional part or too large for an integer are floats.
+pub(crate) fn parse_number_literal(input: &mut &str) -> PResult<Number> {
+    (digit1, opt((DOT, digit1)))
+        .take()
+        .verify_map(|number: &str| {
+            number.parse::<u64>().map(Number::Integer).ok().or_else(|| {
+                number
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|float| float.is_finite())
+                    .map(Number::Float)
+            })
+        })
+        .parse_next(input)
+}
+
+/// A combinator which parses a binary operator.
+pub(crate) fn parse_binary_operator(input: &mut &str) -> PResult<BinaryOperator> {
+    trim(alt((
+        PLUS.value(BinaryOperator::Add),
+        MINUS.value(BinaryOperator::Subtract),
+        ASTERISK.value(BinaryOperator::Multiply),
+        SLASH.value(BinaryOperator::Divide),
+        PERCENT.value(BinaryOperator::Remainder),
+        AMPERSAND.value(BinaryOperator::Intersection),
+        VERTICAL_BAR.value(BinaryOperator::Union),
+    )))
+    .parse_next(input)
+}
+
 /// A combinator which parses a list of `Index`
 pub(crate) fn parse_indexes(input: &mut &str) -> PResult<Vec<Index>> {
-    separated(1.., parse_number, trim(COMMA)).parse_next(input)
+    parse_list(1, parse_number).parse_next(input)
 }
 
 /// A combinator which parses a list of keys.
 fn parse_keys<'a>(input: &mut &'a str) -> PResult<Vec<&'a str>> {
-    trim(separated(1.., parse_key, trim(COMMA))).parse_next(input)
+    trim(parse_list(1, parse_key)).parse_next(input)
 }
 
 /// A combinator which parses a list of keys surrounded by curly braces.
 pub(crate) fn parse_multi_key<'a>(input: &mut &'a str) -> PResult<Vec<&'a str>> {
-    delimited(CURLY_BRACKET_OPEN, parse_keys, CURLY_BRACKET_CLOSE).parse_next(input)
+    delimited(
+        expect(CURLY_BRACKET_OPEN),
+        parse_keys,
+        list_end(CURLY_BRACKET_CLOSE),
+    )
+    .parse_next(input)
 }
 
 /// A combinator which parses an array of `Index`
 pub(crate) fn parse_array_index(input: &mut &str) -> PResult<Vec<Index>> {
     delimited(
-        trim(SQUARE_BRACKET_OPEN),
+        trim(expect(SQUARE_BRACKET_OPEN)),
         parse_indexes,
-        trim(SQUARE_BRACKET_CLOSE),
+        list_end(SQUARE_BRACKET_CLOSE),
     )
     .parse_next(input)
 }
@@ -120,19 +388,61 @@ pub(crate) fn parse_array_index(input: &mut &str) -> PResult<Vec<Index>> {
 /// A combinator which parses an array range.
 pub(crate) fn parse_array_range(input: &mut &str) -> PResult<(Option<Index>, Option<Index>)> {
     trim(delimited(
-        trim(SQUARE_BRACKET_OPEN),
-        separated_pair(opt(parse_number), trim(COLON), opt(parse_number)),
-        trim(SQUARE_BRACKET_CLOSE),
+        trim(expect(SQUARE_BRACKET_OPEN)),
+        separated_pair(opt(parse_number), trim(expect(COLON)), opt(parse_number)),
+        trim(expect(SQUARE_BRACKET_CLOSE)),
     ))
     .parse_next(input)
 }
 
+/// A combinator which parses a range without delimiters.
+fn parse_range(input: &mut &str) -> PResult<Range> {
+    separated_pair(opt(parse_number), trim(expect(COLON)), opt(parse_number))
+        .map(|(start, end)| Range(start, end))
+        .parse_next(input)
+}
+
+/// A combinator which parses a list of indexes and ranges.
+fn parse_selections(input: &mut &str) -> PResult<Vec<Selection>> {
+    parse_list(
+        1,
+        trim(alt((
+            parse_range.map(Selection::Range),
+            parse_number.map(Selection::Index),
+            parse_negative_number.map(Selection::IndexFromEnd),
+        ))),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a list of indexes and ranges surrounded by
+/// square brackets.
+pub(crate) fn parse_array_mixed(input: &mut &str) -> PResult<Vec<Selection>> {
+    delimited(
+        trim(expect(SQUARE_BRACKET_OPEN)),
+        parse_selections,
+        list_end(SQUARE_BRACKET_CLOSE),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a list of indexes and ranges surrounded by curly
+/// braces.
+pub(crate) fn parse_object_mixed(input: &mut &str) -> PResult<Vec<Selection>> {
+    delimited(
+        trim(expect(CURLY_BRACKET_OPEN)),
+        parse_selections,
+        list_end(CURLY_BRACKET_CLOSE),
+    )
+    .parse_next(input)
+}
+
 /// A combinator which parses a list of index surrounded by curly braces.
 pub(crate) fn parse_object_index(input: &mut &str) -> PResult<Vec<Index>> {
     delimited(
-        trim(CURLY_BRACKET_OPEN),
+        trim(expect(CURLY_BRACKET_OPEN)),
         parse_indexes,
-        trim(CURLY_BRACKET_CLOSE),
+        list_end(CURLY_BRACKET_CLOSE),
     )
     .parse_next(input)
 }
@@ -140,10 +450,26 @@ pub(crate) fn parse_object_index(input: &mut &str) -> PResult<Vec<Index>> {
 /// A combinator which parses an object range.
 pub(crate) fn parse_object_range(input: &mut &str) -> PResult<(Option<Index>, Option<Index>)> {
     delimited(
-        trim(CURLY_BRACKET_OPEN),
-        separated_pair(opt(parse_number), trim(COLON), opt(parse_number)),
-        trim(CURLY_BRACKET_CLOSE),
+        trim(expect(CURLY_BRACKET_OPEN)),
+        separated_pair(opt(parse_number), trim(expect(COLON)), opt(parse_number)),
+        trim(expect(CURLY_BRACKET_CLOSE)),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses an object range bounded by keys.
+/// The end key is included with a colon and excluded with a double dot.
+pub(crate) fn parse_object_key_range<'a>(input: &mut &'a str) -> PResult<KeyRange<'a>> {
+    delimited(
+        trim(expect(CURLY_BRACKET_OPEN)),
+        (
+            parse_key,
+            trim(alt((COLON.value(true), EXCLUSIVE_RANGE_SEP.value(false)))),
+            parse_key,
+        ),
+        trim(expect(CURLY_BRACKET_CLOSE)),
     )
+    .map(|(start, inclusive, end)| KeyRange(start, end, inclusive))
     .parse_next(input)
 }
 
@@ -154,6 +480,7 @@ fn parse_lens_key<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
             alt((
                 parse_array_index.map(Token::ArrayIndexSelector),
                 parse_array_range.map(|(start, end)| Token::ArrayRangeSelector(Range(start, end))),
+                parse_array_mixed.map(Token::ArrayMixedSelector),
             ))
         },
         '"' => parse_key.map(Token::KeySelector),
@@ -162,11 +489,16 @@ fn parse_lens_key<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
                 parse_multi_key.map(Token::MultiKeySelector),
                 parse_object_index.map(Token::ObjectIndexSelector),
                 parse_object_range.map(|(start, end)| Token::ObjectRangeSelector(Range(start, end))),
+                parse_object_key_range.map(Token::ObjectKeyRangeSelector),
+                parse_object_mixed.map(Token::ObjectMixedSelector),
             ))
         },
         _ => fail
-    })
-        .parse_next(input)
+    }
+    .context(StrContext::Expected(StrContextValue::Description(
+        "a selector",
+    ))))
+    .parse_next(input)
 }
 
 /// A combinator which parses multiple lens keys.
@@ -176,10 +508,12 @@ fn parse_lens_keys<'a>(input: &mut &'a str) -> PResult<Vec<Token<'a>>> {
 /// A combinator which parses any lens value.
 pub(crate) fn parse_lens_value<'a>(input: &mut &'a str) -> PResult<LensValue<'a>> {
     dispatch! {peek(any);
-        'f' => FALSE.value(LensValue::Bool(false)),
-        't' => TRUE.value(LensValue::Bool(true)),
-        'n' => "null".value(LensValue::Null),
+        'e' => keyword(EXISTS).value(LensValue::Exists),
+        'f' => keyword(FALSE).value(LensValue::Bool(false)),
+        't' => keyword(TRUE).value(LensValue::Bool(true)),
+        'n' => keyword("null").value(LensValue::Null),
         '0'..='9' => digit1.try_map(|s: &str| s.parse::<usize>().map(LensValue::Number)),
+        '<' => parse_json_type.map(LensValue::Type),
         _ => parse_key.map(LensValue::String),
     }
     .parse_next(input)
@@ -191,7 +525,7 @@ pub(crate) fn parse_lens<'a>(
 ) -> PResult<(Vec<Token<'a>>, Option<LensValue<'a>>)> {
     trim((
         parse_lens_keys,
-        opt(preceded(trim(EQUAL), parse_lens_value)),
+        opt(preceded(trim(expect(EQUAL)), parse_lens_value)),
     ))
     .parse_next(input)
 }
@@ -201,13 +535,305 @@ pub(crate) fn parse_lenses<'a>(
     input: &mut &'a str,
 ) -> PResult<Vec<(Vec<Token<'a>>, Option<LensValue<'a>>)>> {
     delimited(
-        trim(LENSES_START),
-        separated(1.., parse_lens, trim(COMMA)),
-        trim(CURLY_BRACKET_CLOSE),
+        trim(expect_literal(LENSES_START)),
+        parse_list(1, parse_lens),
+        list_end(CURLY_BRACKET_CLOSE),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a has operator with either a key or an index.
+pub(crate) fn parse_has_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    preceded(
+        HAS,
+        delimited(
+            trim(expect(PARENTHESIS_OPEN)),
+            alt((
+                parse_key.map(Token::HasKeyOperator),
+                parse_number.map(Token::HasIndexOperator),
+            )),
+            trim(expect(PARENTHESIS_CLOSE)),
+        ),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a conditional.
+/// Both branches are parsed with the provided `branch` parser.
+pub(crate) fn parse_conditional<'a>(
+    branch: fn(&mut &'a str) -> PResult<Vec<Token<'a>>>,
+) -> impl Parser<&'a str, Token<'a>, SpanError> {
+    (
+        preceded(trim(expect_keyword(IF)), parse_list(1, parse_lens)),
+        preceded(trim(expect_keyword(THEN)), branch),
+        delimited(
+            trim(expect_keyword(ELSE)),
+            branch,
+            trim(expect_keyword(END)),
+        ),
+    )
+        .map(
+            |(lenses, consequent, alternative): (
+                Vec<(Vec<Token<'a>>, Option<LensValue<'a>>)>,
+                _,
+                _,
+            )| {
+                Token::Conditional(
+                    lenses
+                        .into_iter()
+                        .map(|(tokens, value)| Lens(tokens, value))
+                        .collect(),
+                    consequent,
+                    alternative,
+                )
+            },
+        )
+}
+
+/// A combinator which parses a sub-query surrounded by parentheses.
+pub(crate) fn parse_sub_query<'a>(input: &mut &'a str) -> PResult<Vec<Token<'a>>> {
+    delimited(
+        trim(expect(PARENTHESIS_OPEN)),
+        parse_lens_keys,
+        trim(expect(PARENTHESIS_CLOSE)),
     )
     .parse_next(input)
 }
 
+/// A combinator which parses at least two comma separated sub-queries
+/// surrounded by parentheses.
+pub(crate) fn parse_sub_queries<'a>(input: &mut &'a str) -> PResult<Vec<Vec<Token<'a>>>> {
+    delimited(
+        trim(expect(PARENTHESIS_OPEN)),
+        parse_list(2, parse_lens_keys),
+        list_end(PARENTHESIS_CLOSE),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a merge or a merge with concatenation operator.
+pub(crate) fn parse_merge_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        preceded(MERGE_CONCAT, parse_sub_queries)
+            .map(|sub_queries| Token::MergeOperator(sub_queries, MergeStrategy::Concatenate)),
+        preceded(MERGE, parse_sub_queries)
+            .map(|sub_queries| Token::MergeOperator(sub_queries, MergeStrategy::Replace)),
+    ))
+    .parse_next(input)
+}
+
+/// A combinator which parses an optional padding suffix.
+fn parse_alignment(input: &mut &str) -> PResult<Alignment> {
+    opt(PAD)
+        .map(|pad| {
+            if pad.is_some() {
+                Alignment::Pad
+            } else {
+                Alignment::Strict
+            }
+        })
+        .parse_next(input)
+}
+
+/// A combinator which parses a zip or a zip object operator.
+pub(crate) fn parse_zip_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        preceded(ZIP_OBJECT, (parse_alignment, parse_sub_queries))
+            .map(|(alignment, sub_queries)| Token::ZipObjectOperator(sub_queries, alignment)),
+        preceded(ZIP, (parse_alignment, parse_sub_queries))
+            .map(|(alignment, sub_queries)| Token::ZipOperator(sub_queries, alignment)),
+    ))
+    .parse_next(input)
+}
+
+/// A combinator which parses a transpose operator.
+pub(crate) fn parse_transpose_operator(input: &mut &str) -> PResult<Alignment> {
+    parse_word
+        .verify_map(|word: &str| parse_alignment.parse(word.strip_prefix(TRANSPOSE)?).ok())
+        .parse_next(input)
+}
+
+/// A combinator which parses a count argument surrounded by parentheses.
+fn parse_count_argument(input: &mut &str) -> PResult<usize> {
+    delimited(
+        trim(expect(PARENTHESIS_OPEN)),
+        parse_number,
+        trim(expect(PARENTHESIS_CLOSE)),
+    )
+    .map(usize::from)
+    .parse_next(input)
+}
+
+/// A combinator which parses a limit operator.
+pub(crate) fn parse_limit_operator(input: &mut &str) -> PResult<usize> {
+    preceded(LIMIT, parse_count_argument).parse_next(input)
+}
+
+/// A combinator which parses a skip operator.
+pub(crate) fn parse_skip_operator(input: &mut &str) -> PResult<usize> {
+    preceded(SKIP, parse_count_argument).parse_next(input)
+}
+
+/// A combinator which parses a first operator.
+pub(crate) fn parse_first_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(FIRST).parse_next(input)
+}
+
+/// A combinator which parses a last operator.
+pub(crate) fn parse_last_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(LAST).parse_next(input)
+}
+
+/// A combinator which parses a string argument surrounded by parentheses.
+fn parse_string_argument<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    delimited(
+        trim(expect(PARENTHESIS_OPEN)),
+        parse_key,
+        trim(expect(PARENTHESIS_CLOSE)),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a split operator.
+pub(crate) fn parse_split_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    preceded(SPLIT, parse_string_argument).parse_next(input)
+}
+
+/// A combinator which parses a join operator.
+pub(crate) fn parse_join_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    preceded(JOIN, parse_string_argument).parse_next(input)
+}
+
+/// A combinator which parses a lower operator.
+pub(crate) fn parse_lower_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(LOWER).parse_next(input)
+}
+
+/// A combinator which parses an upper operator.
+pub(crate) fn parse_upper_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(UPPER).parse_next(input)
+}
+
+/// A combinator which parses a trim operator.
+pub(crate) fn parse_trim_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(TRIM).parse_next(input)
+}
+
+/// A combinator which parses a replace operator.
+pub(crate) fn parse_replace_operator<'a>(input: &mut &'a str) -> PResult<(&'a str, &'a str)> {
+    preceded(
+        REPLACE,
+        delimited(
+            trim(expect(PARENTHESIS_OPEN)),
+            separated_pair(parse_key, trim(expect(COMMA)), parse_key),
+            trim(expect(PARENTHESIS_CLOSE)),
+        ),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses a to string, a to number, a to JSON or a trim
+/// operator.
+pub(crate) fn parse_conversion_or_trim_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        keyword(TO_STRING).value(Token::ToStringOperator),
+        keyword(TO_NUMBER).value(Token::ToNumberOperator),
+        keyword(TO_JSON).value(Token::ToJsonOperator),
+        parse_trim_operator.value(Token::TrimOperator),
+    ))
+    .parse_next(input)
+}
+
+/// A combinator which parses a from JSON operator.
+pub(crate) fn parse_from_json_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(FROM_JSON).parse_next(input)
+}
+
+/// A combinator which parses a type operator.
+pub(crate) fn parse_type_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(TYPE).parse_next(input)
+}
+
+/// A combinator which parses a JSON type surrounded by angle brackets.
+pub(crate) fn parse_json_type(input: &mut &str) -> PResult<JsonType> {
+    delimited(
+        trim(expect(ANGLE_BRACKET_OPEN)),
+        alt((
+            "array".value(JsonType::Array),
+            "boolean".value(JsonType::Boolean),
+            "null".value(JsonType::Null),
+            "number".value(JsonType::Number),
+            "object".value(JsonType::Object),
+            "string".value(JsonType::String),
+        ))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a JSON type",
+        ))),
+        trim(expect(ANGLE_BRACKET_CLOSE)),
+    )
+    .parse_next(input)
+}
+
+/// A combinator which parses an optional descending order suffix.
+fn parse_order(input: &mut &str) -> PResult<Order> {
+    opt(DESCENDING)
+        .map(|descending| {
+            if descending.is_some() {
+                Order::Descending
+            } else {
+                Order::Ascending
+            }
+        })
+        .parse_next(input)
+}
+
+/// A combinator which parses a sort or a sort by operator.
+pub(crate) fn parse_sort_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        preceded(SORT_BY, (parse_order, parse_sub_query))
+            .map(|(order, tokens)| Token::SortByOperator(tokens, order)),
+        parse_word
+            .verify_map(|word: &str| parse_order.parse(word.strip_prefix(SORT)?).ok())
+            .map(Token::SortOperator),
+    ))
+    .parse_next(input)
+}
+
+/// A combinator which parses a unique or a unique by operator.
+pub(crate) fn parse_unique_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        preceded(UNIQUE_BY, parse_sub_query).map(Token::UniqueByOperator),
+        keyword(UNIQUE).value(Token::UniqueOperator),
+    ))
+    .parse_next(input)
+}
+
+/// A combinator which parses a group by operator.
+pub(crate) fn parse_group_by_operator<'a>(input: &mut &'a str) -> PResult<Vec<Token<'a>>> {
+    preceded(GROUP_BY, parse_sub_query).parse_next(input)
+}
+
+/// A combinator which parses a sum operator.
+pub(crate) fn parse_sum_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(SUM).parse_next(input)
+}
+
+/// A combinator which parses an average operator.
+pub(crate) fn parse_average_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(AVERAGE).parse_next(input)
+}
+
+/// A combinator which parses a min, a min by, a max or a max by operator.
+pub(crate) fn parse_min_max_operator<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        preceded(MIN_BY, parse_sub_query).map(Token::MinByOperator),
+        keyword(MIN).value(Token::MinOperator),
+        preceded(MAX_BY, parse_sub_query).map(Token::MaxByOperator),
+        keyword(MAX).value(Token::MaxOperator),
+    ))
+    .parse_next(input)
+}
+
 /// A combinator which parses a keys operator.
 pub(crate) fn parse_keys_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
     literal(KEYS).parse_next(input)
@@ -218,6 +844,16 @@ pub(crate) fn parse_flatten_operator<'a>(input: &mut &'a str) -> PResult<&'a str
     literal(FLATTEN).parse_next(input)
 }
 
+/// A combinator which parses a values operator.
+pub(crate) fn parse_values_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(VALUES).parse_next(input)
+}
+
+/// A combinator which parses a descendants operator.
+pub(crate) fn parse_descendants_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    keyword(DESCENDANTS).parse_next(input)
+}
+
 /// A combinator which parses a pipe in operator.
 pub(crate) fn parse_pipe_in_operator<'a>(input: &mut &'a str) -> PResult<&'a str> {
     literal(PIPE_IN).parse_next(input)
@@ -240,33 +876,94 @@ pub(crate) fn parse_group_separator<'a>(input: &mut &'a str) -> PResult<&'a str>
 
 #[cfg(test)]
 mod tests {
+    use winnow::{
+        Parser,
+        error::ContextError,
+    };
+
     use super::{
+        AVERAGE,
+        DESCENDANTS,
+        FIRST,
         FLATTEN,
+        FROM_JSON,
         GROUP_SEP,
         KEYS,
+        LAST,
+        LOWER,
         PIPE_IN,
         PIPE_OUT,
+        SUM,
+        TRIM,
         TRUNCATE,
+        TYPE,
+        UPPER,
+        VALUES,
         parse_array_index,
+        parse_array_mixed,
         parse_array_range,
+        parse_average_operator,
+        parse_binary_operator,
+        parse_conversion_or_trim_operator,
+        parse_descendants_operator,
+        parse_first_operator,
         parse_flatten_operator,
+        parse_from_json_operator,
+        parse_group_by_operator,
         parse_group_separator,
+        parse_has_operator,
         parse_indexes,
+        parse_join_operator,
+        parse_json_type,
         parse_key,
         parse_keys_operator,
+        parse_last_operator,
         parse_lens,
         parse_lenses,
+        parse_limit_operator,
+        parse_list,
+        parse_lower_operator,
+        parse_merge_operator,
+        parse_min_max_operator,
         parse_multi_key,
         parse_number,
+        parse_number_literal,
         parse_object_index,
+        parse_object_key_range,
+        parse_object_mixed,
         parse_object_range,
         parse_pipe_in_operator,
         parse_pipe_out_operator,
+        parse_replace_operator,
+        parse_skip_operator,
+        parse_sort_operator,
+        parse_split_operator,
+        parse_sub_queries,
+        parse_sub_query,
+        parse_sum_operator,
+        parse_transpose_operator,
+        parse_trim_operator,
         parse_truncate_operator,
+        parse_type_operator,
+        parse_unique_operator,
+        parse_upper_operator,
+        parse_values_operator,
+        parse_whitespace,
+        parse_zip_operator,
+        trim,
     };
     use crate::tokens::{
+        Alignment,
+        BinaryOperator,
         Index,
+        JsonType,
+        KeyRange,
         LensValue,
+        MergeStrategy,
+        Number,
+        Order,
+        Range,
+        Selection,
         Token,
     };
 
@@ -277,6 +974,69 @@ mod tests {
         assert!(parse_number(&mut "abc123").is_err());
     }
 
+    #[test]
+    fn check_parse_number_literal() {
+        assert_eq!(Ok(Number::Integer(123)), parse_number_literal(&mut "123"));
+        assert_eq!(Ok(Number::Float(1.5)), parse_number_literal(&mut "1.5"));
+        assert_eq!(
+            Ok(Number::Float(1e20)),
+            parse_number_literal(&mut "100000000000000000000")
+        );
+        assert!(parse_number_literal(&mut "abc").is_err());
+        assert!(parse_number_literal(&mut ".5").is_err());
+
+        let mut input = "1..";
+        assert_eq!(Ok(Number::Integer(1)), parse_number_literal(&mut input));
+        assert_eq!(input, "..");
+    }
+
+    #[test]
+    fn check_parse_binary_operator() {
+        assert_eq!(Ok(BinaryOperator::Add), parse_binary_operator(&mut " + "));
+        assert_eq!(
+            Ok(BinaryOperator::Subtract),
+            parse_binary_operator(&mut "-")
+        );
+        assert_eq!(
+            Ok(BinaryOperator::Multiply),
+            parse_binary_operator(&mut "*")
+        );
+        assert_eq!(Ok(BinaryOperator::Divide), parse_binary_operator(&mut "/"));
+        assert_eq!(
+            Ok(BinaryOperator::Remainder),
+            parse_binary_operator(&mut "%")
+        );
+        assert_eq!(
+            Ok(BinaryOperator::Intersection),
+            parse_binary_operator(&mut "&")
+        );
+        assert_eq!(Ok(BinaryOperator::Union), parse_binary_operator(&mut "|"));
+        assert!(parse_binary_operator(&mut "").is_err());
+    }
+
+    #[test]
+    fn check_parse_whitespace() {
+        let mut input = " # line\n\t/* block\n */ # end";
+        assert_eq!(Ok(()), parse_whitespace::<ContextError>(&mut input));
+        assert_eq!(input, "");
+
+        let mut input = r#" /* "a" */ "b""#;
+        assert_eq!(Ok(()), parse_whitespace::<ContextError>(&mut input));
+        assert_eq!(input, r#""b""#);
+
+        let mut input = "/* unterminated";
+        assert_eq!(Ok(()), parse_whitespace::<ContextError>(&mut input));
+        assert_eq!(input, "/* unterminated");
+    }
+
+    #[test]
+    fn check_trim_comments() {
+        assert_eq!(
+            Ok("a # b"),
+            trim(parse_key).parse_next(&mut "# comment\n\"a # b\" /* c */")
+        );
+    }
+
     #[test]
     fn check_parse_key() {
         assert_eq!(Ok("abc"), parse_key(&mut r#""abc""#));
@@ -293,6 +1053,24 @@ mod tests {
         assert!(parse_indexes(&mut "abc").is_err());
     }
 
+    #[test]
+    fn check_parse_list() {
+        assert_eq!(
+            Ok(vec![Index(1), Index(2)]),
+            parse_list(2, parse_number).parse_next(&mut "1 , 2")
+        );
+        assert!(parse_list(2, parse_number).parse_next(&mut "1").is_err());
+
+        let input = "1,2,";
+        let error = parse_list(1, parse_number)
+            .parse_next(&mut { input })
+            .unwrap_err()
+            .into_inner()
+            .unwrap();
+        assert_eq!(error.offset(input), 4);
+        assert_eq!(error.expected(), vec!["a number"]);
+    }
+
     #[test]
     fn check_parse_multi_key() {
         assert_eq!(Ok(vec!["abc"]), parse_multi_key(&mut r#"{"abc"}"#));
@@ -350,6 +1128,56 @@ mod tests {
         assert!(parse_object_range(&mut "{1:3").is_err());
     }
 
+    #[test]
+    fn check_parse_mixed_selections() {
+        assert_eq!(
+            Ok(vec![
+                Selection::Index(Index(0)),
+                Selection::Range(Range(Some(Index(3)), Some(Index(6)))),
+                Selection::Index(Index(9)),
+            ]),
+            parse_array_mixed(&mut "[0, 3:6, 9]"),
+        );
+        assert_eq!(
+            Ok(vec![
+                Selection::Range(Range(None, Some(Index(1)))),
+                Selection::Index(Index(2)),
+            ]),
+            parse_object_mixed(&mut "{:1,2}"),
+        );
+        assert_eq!(
+            Ok(vec![
+                Selection::Index(Index(0)),
+                Selection::Range(Range(Some(Index(3)), Some(Index(6)))),
+                Selection::IndexFromEnd(Index(1)),
+            ]),
+            parse_array_mixed(&mut "[0, 3:6, -1]"),
+        );
+        assert_eq!(
+            Ok(vec![Selection::IndexFromEnd(Index(2))]),
+            parse_object_mixed(&mut "{- 2}"),
+        );
+        assert!(parse_array_mixed(&mut "[-0]").is_err());
+        assert!(parse_array_mixed(&mut "[-1:]").is_err());
+        assert!(parse_array_mixed(&mut "[0, 3:6").is_err());
+        assert!(parse_object_mixed(&mut "{0, }").is_err());
+    }
+
+    #[test]
+    fn check_parse_object_key_range() {
+        assert_eq!(
+            Ok(KeyRange("b", "f", true)),
+            parse_object_key_range(&mut r#"{"b":"f"}"#),
+        );
+        assert_eq!(
+            Ok(KeyRange("b", "f", false)),
+            parse_object_key_range(&mut r#" { "b" .. "f" } "#),
+        );
+        assert!(parse_object_key_range(&mut r#"{"b":}"#).is_err());
+        assert!(parse_object_key_range(&mut r#"{"b"."f"}"#).is_err());
+        assert!(parse_object_key_range(&mut r#"{"b":"f""#).is_err());
+    }
+
     #[test]
     fn check_parse_keys_operator() {
         assert_eq!(Ok(KEYS), parse_keys_operator(&mut "@"));
@@ -386,6 +1214,266 @@ mod tests {
         assert!(parse_group_separator(&mut "").is_err());
     }
 
+    #[test]
+    fn check_parse_sub_query() {
+        assert_eq!(
+            Ok(vec![Token::KeySelector("a"), Token::KeySelector("b")]),
+            parse_sub_query(&mut r#"("a""b")"#)
+        );
+        assert_eq!(
+            Ok(vec![Token::KeySelector("a")]),
+            parse_sub_query(&mut r#"( "a" )"#)
+        );
+        assert!(parse_sub_query(&mut "()").is_err());
+        assert!(parse_sub_query(&mut r#"("a""#).is_err());
+    }
+
+    #[test]
+    fn check_parse_sort_operator() {
+        assert_eq!(
+            Ok(Token::SortOperator(Order::Ascending)),
+            parse_sort_operator(&mut "sort")
+        );
+        assert_eq!(
+            Ok(Token::SortOperator(Order::Descending)),
+            parse_sort_operator(&mut "sort_desc")
+        );
+        assert_eq!(
+            Ok(Token::SortByOperator(
+                vec![Token::KeySelector("a"), Token::KeySelector("b")],
+                Order::Ascending
+            )),
+            parse_sort_operator(&mut r#"sort_by("a""b")"#)
+        );
+        assert_eq!(
+            Ok(Token::SortByOperator(
+                vec![Token::KeySelector("a")],
+                Order::Descending
+            )),
+            parse_sort_operator(&mut r#"sort_by_desc("a")"#)
+        );
+        assert!(parse_sort_operator(&mut "").is_err());
+        assert!(parse_sort_operator(&mut "sort_by").is_err());
+        assert!(parse_sort_operator(&mut "sorted").is_err());
+    }
+
+    #[test]
+    fn check_parse_unique_operator() {
+        assert_eq!(
+            Ok(Token::UniqueOperator),
+            parse_unique_operator(&mut "unique")
+        );
+        assert_eq!(
+            Ok(Token::UniqueByOperator(vec![
+                Token::KeySelector("a"),
+                Token::ArrayIndexSelector(vec![Index(0)])
+            ])),
+            parse_unique_operator(&mut r#"unique_by("a"[0])"#)
+        );
+        assert!(parse_unique_operator(&mut "uniq").is_err());
+    }
+
+    #[test]
+    fn check_parse_group_by_operator() {
+        assert_eq!(
+            Ok(vec![Token::KeySelector("a")]),
+            parse_group_by_operator(&mut r#"group_by("a")"#)
+        );
+        assert!(parse_group_by_operator(&mut "group_by").is_err());
+        assert!(parse_group_by_operator(&mut "group_by()").is_err());
+    }
+
+    #[test]
+    fn check_parse_values_operator() {
+        assert_eq!(Ok(VALUES), parse_values_operator(&mut "values"));
+        assert!(parse_values_operator(&mut "value").is_err());
+    }
+
+    #[test]
+    fn check_parse_descendants_operator() {
+        assert_eq!(
+            Ok(DESCENDANTS),
+            parse_descendants_operator(&mut "descendants")
+        );
+        assert!(parse_descendants_operator(&mut "").is_err());
+    }
+
+    #[test]
+    fn check_parse_sum_operator() {
+        assert_eq!(Ok(SUM), parse_sum_operator(&mut "sum"));
+        assert!(parse_sum_operator(&mut "").is_err());
+    }
+
+    #[test]
+    fn check_parse_average_operator() {
+        assert_eq!(Ok(AVERAGE), parse_average_operator(&mut "avg"));
+        assert!(parse_average_operator(&mut "").is_err());
+    }
+
+    #[test]
+    fn check_parse_min_max_operator() {
+        assert_eq!(Ok(Token::MinOperator), parse_min_max_operator(&mut "min"));
+        assert_eq!(Ok(Token::MaxOperator), parse_min_max_operator(&mut "max"));
+        assert_eq!(
+            Ok(Token::MinByOperator(vec![Token::KeySelector("a")])),
+            parse_min_max_operator(&mut r#"min_by("a")"#)
+        );
+        assert_eq!(
+            Ok(Token::MaxByOperator(vec![Token::KeySelector("a")])),
+            parse_min_max_operator(&mut r#"max_by("a")"#)
+        );
+        assert!(parse_min_max_operator(&mut "mean").is_err());
+    }
+
+    #[test]
+    fn check_parse_string_operators() {
+        assert_eq!(Ok(","), parse_split_operator(&mut r#"split(",")"#));
+        assert_eq!(Ok(""), parse_split_operator(&mut r#"split( "" )"#));
+        assert!(parse_split_operator(&mut "split()").is_err());
+        assert_eq!(Ok(", "), parse_join_operator(&mut r#"join(", ")"#));
+        assert!(parse_join_operator(&mut "join(1)").is_err());
+        assert_eq!(Ok(LOWER), parse_lower_operator(&mut "lower"));
+        assert_eq!(Ok(UPPER), parse_upper_operator(&mut "upper"));
+        assert_eq!(Ok(TRIM), parse_trim_operator(&mut "trim"));
+        assert_eq!(
+            Ok(("a", "b")),
+            parse_replace_operator(&mut r#"replace("a", "b")"#)
+        );
+        assert!(parse_replace_operator(&mut r#"replace("a")"#).is_err());
+    }
+
+    #[test]
+    fn check_parse_conversion_operators() {
+        assert_eq!(
+            Ok(Token::ToStringOperator),
+            parse_conversion_or_trim_operator(&mut "tostring")
+        );
+        assert_eq!(
+            Ok(Token::ToNumberOperator),
+            parse_conversion_or_trim_operator(&mut "tonumber")
+        );
+        assert_eq!(
+            Ok(Token::ToJsonOperator),
+            parse_conversion_or_trim_operator(&mut "tojson")
+        );
+        assert_eq!(
+            Ok(Token::TrimOperator),
+            parse_conversion_or_trim_operator(&mut "trim")
+        );
+        assert!(parse_conversion_or_trim_operator(&mut "toyaml").is_err());
+        assert_eq!(Ok(FROM_JSON), parse_from_json_operator(&mut "fromjson"));
+        assert!(parse_from_json_operator(&mut "from").is_err());
+    }
+
+    #[test]
+    fn check_parse_merge_operator() {
+        assert_eq!(
+            Ok(vec![
+                vec![Token::KeySelector("a")],
+                vec![Token::KeySelector("b"), Token::KeySelector("c")],
+            ]),
+            parse_sub_queries(&mut r#"( "a" , "b""c" )"#)
+        );
+        assert!(parse_sub_queries(&mut r#"("a")"#).is_err());
+        assert_eq!(
+            Ok(Token::MergeOperator(
+                vec![vec![Token::KeySelector("a")], vec![Token::KeySelector("b")]],
+                MergeStrategy::Replace
+            )),
+            parse_merge_operator(&mut r#"merge("a","b")"#)
+        );
+        assert_eq!(
+            Ok(Token::MergeOperator(
+                vec![vec![Token::KeySelector("a")], vec![Token::KeySelector("b")]],
+                MergeStrategy::Concatenate
+            )),
+            parse_merge_operator(&mut r#"merge_concat("a","b")"#)
+        );
+    }
+
+    #[test]
+    fn check_parse_slice_operators() {
+        assert_eq!(Ok(10), parse_limit_operator(&mut "limit(10)"));
+        assert_eq!(Ok(0), parse_limit_operator(&mut "limit( 0 )"));
+        assert!(parse_limit_operator(&mut "limit(-1)").is_err());
+        assert_eq!(Ok(2), parse_skip_operator(&mut "skip(2)"));
+        assert!(parse_skip_operator(&mut "skip").is_err());
+        assert_eq!(Ok(FIRST), parse_first_operator(&mut "first"));
+        assert_eq!(Ok(LAST), parse_last_operator(&mut "last"));
+        assert!(parse_first_operator(&mut "firstly").is_err());
+        assert!(parse_last_operator(&mut "last_1").is_err());
+
+        let mut input = r#"first"a""#;
+        assert_eq!(Ok(FIRST), parse_first_operator(&mut input));
+        assert_eq!(input, r#""a""#);
+    }
+
+    #[test]
+    fn check_parse_zip_operator() {
+        let sub_queries = vec![vec![Token::KeySelector("a")], vec![Token::KeySelector("b")]];
+
+        assert_eq!(
+            Ok(Token::ZipOperator(sub_queries.clone(), Alignment::Strict)),
+            parse_zip_operator(&mut r#"zip("a","b")"#)
+        );
+        assert_eq!(
+            Ok(Token::ZipOperator(sub_queries.clone(), Alignment::Pad)),
+            parse_zip_operator(&mut r#"zip_pad("a","b")"#)
+        );
+        assert_eq!(
+            Ok(Token::ZipObjectOperator(
+                sub_queries.clone(),
+                Alignment::Strict
+            )),
+            parse_zip_operator(&mut r#"zip_object("a","b")"#)
+        );
+        assert_eq!(
+            Ok(Token::ZipObjectOperator(sub_queries, Alignment::Pad)),
+            parse_zip_operator(&mut r#"zip_object_pad("a","b")"#)
+        );
+        assert!(parse_zip_operator(&mut "zip").is_err());
+    }
+
+    #[test]
+    fn check_parse_transpose_operator() {
+        assert_eq!(
+            Ok(Alignment::Strict),
+            parse_transpose_operator(&mut "transpose")
+        );
+        assert_eq!(
+            Ok(Alignment::Pad),
+            parse_transpose_operator(&mut "transpose_pad")
+        );
+        assert!(parse_transpose_operator(&mut "trans").is_err());
+    }
+
+    #[test]
+    fn check_parse_has_operator() {
+        assert_eq!(
+            Ok(Token::HasKeyOperator("a")),
+            parse_has_operator(&mut r#"has("a")"#)
+        );
+        assert_eq!(
+            Ok(Token::HasIndexOperator(Index(3))),
+            parse_has_operator(&mut "has( 3 )")
+        );
+        assert!(parse_has_operator(&mut "has()").is_err());
+        assert!(parse_has_operator(&mut "has(a)").is_err());
+    }
+
+    #[test]
+    fn check_parse_type() {
+        assert_eq!(Ok(TYPE), parse_type_operator(&mut "type"));
+        assert_eq!(Ok(JsonType::Array), parse_json_type(&mut "<array>"));
+        assert_eq!(Ok(JsonType::Boolean), parse_json_type(&mut "< boolean >"));
+        assert_eq!(Ok(JsonType::Null), parse_json_type(&mut "<null>"));
+        assert_eq!(Ok(JsonType::Number), parse_json_type(&mut "<number>"));
+        assert_eq!(Ok(JsonType::Object), parse_json_type(&mut "<object>"));
+        assert_eq!(Ok(JsonType::String), parse_json_type(&mut "<string>"));
+        assert!(parse_json_type(&mut "<bool>").is_err());
+        assert!(parse_json_type(&mut "<|").is_err());
+    }
+
     #[test]
     fn check_parse_lens() {
         assert_eq!(
@@ -410,6 +1498,17 @@ mod tests {
             )),
             parse_lens(&mut r#""abc"="def""#),
         );
+        assert_eq!(
+            Ok((
+                vec![Token::KeySelector("abc")],
+                Some(LensValue::Type(JsonType::String))
+            )),
+            parse_lens(&mut r#""abc"=<string>"#),
+        );
+        assert_eq!(
+            Ok((vec![Token::KeySelector("abc")], Some(LensValue::Exists))),
+            parse_lens(&mut r#""abc"=exists"#),
+        );
         assert!(parse_lenses(&mut "").is_err());
     }
 
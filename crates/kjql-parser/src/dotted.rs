@@ -0,0 +1,222 @@
+use winnow::{
+    Parser,
+    ascii::digit1,
+    combinator::{
+        alt,
+        opt,
+        preceded,
+        repeat,
+        separated,
+    },
+    error::{
+        ErrMode,
+        StrContext,
+        StrContextValue,
+    },
+    token::{
+        one_of,
+        take_while,
+    },
+};
+
+use crate::{
+    combinators::{
+        parse_array_index,
+        parse_array_mixed,
+        parse_array_range,
+    },
+    errors::{
+        KjqlParserError,
+        PResult,
+        SpanError,
+    },
+    tokens::{
+        Range,
+        Token,
+        View,
+    },
+};
+
+/// Dot.
+static DOT: char = '.';
+
+/// A combinator which parses an unquoted identifier.
+fn parse_identifier<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    (
+        one_of(|c: char| c.is_alphabetic() || c == '_' || c == '$'),
+        take_while(0.., |c: char| {
+            c.is_alphanumeric() || c == '_' || c == '$' || c == '-'
+        }),
+    )
+        .take()
+        .context(StrContext::Expected(StrContextValue::Description(
+            "an identifier",
+        )))
+        .parse_next(input)
+}
+
+/// A combinator which parses a number used as a reference, selecting a key on
+/// objects and an index on arrays.
+fn parse_reference<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    digit1
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a number",
+        )))
+        .parse_next(input)
+}
+
+/// A combinator which parses an index or a range surrounded by square
+/// brackets.
+fn parse_brackets<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
+    alt((
+        parse_array_index.map(Token::ArrayIndexSelector),
+        parse_array_range.map(|(start, end)| Token::ArrayRangeSelector(Range(start, end))),
+        parse_array_mixed.map(Token::ArrayMixedSelector),
+    ))
+    .parse_next(input)
+}
+
+/// A combinator which parses a path segment, an identifier used as a key or a
+/// number used as a reference, followed by optional brackets.
+fn parse_segment<'a>(input: &mut &'a str) -> PResult<Vec<Token<'a>>> {
+    (
+        alt((
+            parse_identifier.map(Token::KeySelector),
+            parse_reference.map(Token::ReferenceSelector),
+        )),
+        repeat(0.., parse_brackets),
+    )
+        .map(|(token, brackets): (Token<'a>, Vec<Token<'a>>)| {
+            let mut tokens = vec![token];
+            tokens.extend(brackets);
+            tokens
+        })
+        .parse_next(input)
+}
+
+/// Parses a dotted path like `devDependencies.react` or `keywords.3` and
+/// returns a vector of `Tokens`.
+/// Identifiers are mapped to keys, numbers to references selecting a key on
+/// objects and an index on arrays, square brackets to indexes.
+///
+/// # Errors
+///
+/// Returns a `KjqlParserError` if the input cannot be parsed.
+pub fn parse_dotted(input: &str) -> Result<Vec<Token<'_>>, KjqlParserError> {
+    let path = input.trim();
+
+    if path.is_empty() {
+        return Err(KjqlParserError::EmptyInputError);
+    }
+
+    // Offsets are reported in the untrimmed input.
+    let leading = input.len() - input.trim_start().len();
+    let parsing_error =
+        |tokens: String, unparsed: &str, error: SpanError| KjqlParserError::ParsingError {
+            tokens,
+            unparsed: unparsed.to_string(),
+            offset: leading + error.offset(path),
+            expected: error.expected(),
+        };
+
+    let mut unparsed = path;
+    let segments: Vec<Vec<Token>> = preceded(opt(DOT), separated(1.., parse_segment, DOT))
+        .parse_next(&mut unparsed)
+        .map_err(|error| {
+            parsing_error(String::new(), path, error.into_inner().unwrap_or_default())
+        })?;
+    let tokens = segments.concat();
+
+    if !unparsed.is_empty() {
+        // The path stopped before the end of the input, parse the next segment
+        // again to locate the failure.
+        let error = preceded(
+            DOT.context(StrContext::Expected(StrContextValue::CharLiteral(DOT)))
+                .context(StrContext::Expected(StrContextValue::CharLiteral('['))),
+            parse_segment,
+        )
+        .parse_next(&mut { unparsed })
+        .err()
+        .and_then(ErrMode::into_inner)
+        .unwrap_or_default();
+
+        return Err(parsing_error(tokens.stringify(), unparsed, error));
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dotted;
+    use crate::{
+        errors::KjqlParserError,
+        tokens::{
+            Index,
+            Range,
+            Token,
+            View,
+        },
+    };
+
+    #[test]
+    fn check_parse_dotted() {
+        assert_eq!(
+            parse_dotted("devDependencies.react"),
+            Ok(vec![
+                Token::KeySelector("devDependencies"),
+                Token::KeySelector("react")
+            ])
+        );
+        assert_eq!(
+            parse_dotted(".keywords.3"),
+            Ok(vec![
+                Token::KeySelector("keywords"),
+                Token::ReferenceSelector("3")
+            ])
+        );
+        assert_eq!(
+            parse_dotted("a_b.$c[0][1:2].d-e"),
+            Ok(vec![
+                Token::KeySelector("a_b"),
+                Token::KeySelector("$c"),
+                Token::ArrayIndexSelector(vec![Index(0)]),
+                Token::ArrayRangeSelector(Range(Some(Index(1)), Some(Index(2)))),
+                Token::KeySelector("d-e"),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_parse_dotted_errors() {
+        assert_eq!(parse_dotted(" "), Err(KjqlParserError::EmptyInputError));
+        assert_eq!(
+            parse_dotted(r#""a""#),
+            Err(KjqlParserError::ParsingError {
+                tokens: String::new(),
+                unparsed: r#""a""#.to_string(),
+                offset: 0,
+                expected: vec!["an identifier".to_string(), "a number".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_dotted(" a..b"),
+            Err(KjqlParserError::ParsingError {
+                tokens: [Token::KeySelector("a")].stringify(),
+                unparsed: "..b".to_string(),
+                offset: 3,
+                expected: vec!["an identifier".to_string(), "a number".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_dotted("a b"),
+            Err(KjqlParserError::ParsingError {
+                tokens: [Token::KeySelector("a")].stringify(),
+                unparsed: " b".to_string(),
+                offset: 1,
+                expected: vec!["`.`".to_string(), "`[`".to_string()],
+            })
+        );
+        assert!(parse_dotted("a.b[").is_err());
+    }
+}
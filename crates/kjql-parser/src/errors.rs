@@ -1,4 +1,112 @@
 use thiserror::Error;
+use winnow::{
+    error::{
+        AddContext,
+        ErrorKind,
+        FromExternalError,
+        ParserError,
+        StrContext,
+        StrContextValue,
+    },
+    stream::{
+        Offset,
+        Stream,
+    },
+};
+
+/// Result type returned by the combinators.
+pub(crate) type PResult<O> = winnow::PResult<O, SpanError>;
+
+/// Error type returned by the combinators.
+/// It tracks the position of the failure and what was expected there.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SpanError {
+    /// Length of the input left at the position of the failure.
+    remaining: usize,
+    /// Tokens expected at the position of the failure.
+    expected: Vec<StrContextValue>,
+}
+
+impl SpanError {
+    /// Creates an error for the provided remaining input expecting the
+    /// provided tokens.
+    pub(crate) fn new(input: &str, expected: Vec<StrContextValue>) -> Self {
+        SpanError {
+            remaining: input.len(),
+            expected,
+        }
+    }
+
+    /// Returns the byte offset of the failure in the provided input.
+    pub(crate) fn offset(&self, input: &str) -> usize {
+        input.len().saturating_sub(self.remaining)
+    }
+
+    /// Returns the descriptions of the expected tokens.
+    pub(crate) fn expected(&self) -> Vec<String> {
+        self.expected.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl<'a> ParserError<&'a str> for SpanError {
+    fn from_error_kind(input: &&'a str, _kind: ErrorKind) -> Self {
+        SpanError::new(input, Vec::new())
+    }
+
+    fn append(
+        self,
+        _input: &&'a str,
+        _token_start: &<&'a str as Stream>::Checkpoint,
+        _kind: ErrorKind,
+    ) -> Self {
+        self
+    }
+
+    /// Keeps the failure which went the furthest, merging the expected tokens
+    /// of failures happening at the same position.
+    fn or(mut self, other: Self) -> Self {
+        match self.remaining.cmp(&other.remaining) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                for value in other.expected {
+                    if !self.expected.contains(&value) {
+                        self.expected.push(value);
+                    }
+                }
+
+                self
+            }
+        }
+    }
+}
+
+impl<'a> AddContext<&'a str, StrContext> for SpanError {
+    /// Records the expected token only if the labelled parser failed without
+    /// consuming any input, deeper failures being more accurate.
+    fn add_context(
+        mut self,
+        input: &&'a str,
+        token_start: &<&'a str as Stream>::Checkpoint,
+        context: StrContext,
+    ) -> Self {
+        if let StrContext::Expected(value) = context {
+            if self.remaining == input.len() + input.offset_from(token_start)
+                && !self.expected.contains(&value)
+            {
+                self.expected.push(value);
+            }
+        }
+
+        self
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for SpanError {
+    fn from_external_error(input: &&'a str, kind: ErrorKind, _error: E) -> Self {
+        SpanError::from_error_kind(input, kind)
+    }
+}
 
 fn display_content(content: &str) -> String {
     if content.is_empty() {
@@ -8,6 +116,14 @@ fn display_content(content: &str) -> String {
     }
 }
 
+fn display_expected(expected: &[String]) -> String {
+    match expected {
+        [] => String::new(),
+        [last] => format!(", expected {last}"),
+        [rest @ .., last] => format!(", expected {} or {last}", rest.join(", ")),
+    }
+}
+
 /// Error type returned by the parser.
 #[derive(Debug, Error, PartialEq)]
 pub enum KjqlParserError {
@@ -15,13 +131,56 @@ pub enum KjqlParserError {
     #[error("Empty input")]
     EmptyInputError,
 
+    /// Expression error.
+    #[error("Binary operator found without a left or a right operand in {0}")]
+    ExpressionError(String),
+
+    /// JSONPath error.
+    #[error("Invalid JSONPath {path}, {reason}")]
+    JsonPathError {
+        /// Path.
+        path: String,
+        /// Reason of the failure.
+        reason: String,
+    },
+
+    /// Literal error.
+    #[error("Number literal found outside of a binary expression operand in {0}")]
+    LiteralError(String),
+
     /// Parser error.
-    #[error("Unable to parse input {unparsed}{}", display_content(tokens))]
+    #[error(
+        "Unable to parse input {unparsed}{} at offset {offset}{}",
+        display_content(tokens),
+        display_expected(expected)
+    )]
     ParsingError {
         /// Tokens found while parsing.
         tokens: String,
         /// Unparsed content.
         unparsed: String,
+        /// Byte offset of the failure in the input.
+        offset: usize,
+        /// Tokens expected at the offset.
+        expected: Vec<String>,
+    },
+
+    /// JSON Pointer error.
+    #[error("Invalid JSON Pointer {pointer}, {reason}")]
+    PointerError {
+        /// Pointer.
+        pointer: String,
+        /// Reason of the failure.
+        reason: String,
+    },
+
+    /// Serializer error.
+    #[error("Unable to serialize {token}, {reason}")]
+    SerializeError {
+        /// Token which can't be serialized.
+        token: String,
+        /// Reason of the failure.
+        reason: String,
     },
 
     /// Truncate error.
@@ -35,11 +194,45 @@ pub enum KjqlParserError {
 
 #[cfg(test)]
 mod tests {
-    use super::display_content;
+    use winnow::error::{
+        ParserError,
+        StrContextValue,
+    };
+
+    use super::{
+        SpanError,
+        display_content,
+        display_expected,
+    };
 
     #[test]
     fn check_display_content() {
         assert_eq!(display_content("some"), " after some");
         assert_eq!(display_content(""), "");
     }
+
+    #[test]
+    fn check_display_expected() {
+        assert_eq!(display_expected(&[]), "");
+        assert_eq!(display_expected(&["`]`".to_string()]), ", expected `]`");
+        assert_eq!(
+            display_expected(&["`]`".to_string(), "`,`".to_string(), "`:`".to_string()]),
+            ", expected `]`, `,` or `:`"
+        );
+    }
+
+    #[test]
+    fn check_span_error_or() {
+        let input = "abc";
+        let close = SpanError::new(&input[1..], vec![StrContextValue::CharLiteral(']')]);
+        let comma = SpanError::new(&input[1..], vec![StrContextValue::CharLiteral(',')]);
+        let deeper = SpanError::new(&input[2..], Vec::new());
+
+        assert_eq!(close.clone().or(deeper.clone()), deeper);
+        assert_eq!(deeper.clone().or(close.clone()), deeper);
+
+        let merged = close.or(comma);
+        assert_eq!(merged.offset(input), 1);
+        assert_eq!(merged.expected(), vec!["`]`", "`,`"]);
+    }
 }
@@ -0,0 +1,317 @@
+use std::fmt;
+
+use crate::{
+    errors::KjqlParserError,
+    tokens::{
+        BinaryOperator,
+        Expression,
+        Token,
+    },
+};
+
+/// Fragment produced by the parser before the expression layer is applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Fragment<'a> {
+    /// Binary operator.
+    Operator(BinaryOperator),
+    /// Token.
+    Token(Token<'a>),
+}
+
+impl fmt::Display for Fragment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fragment::Operator(operator) => write!(f, "BinaryOperator ({operator})"),
+            Fragment::Token(token) => write!(f, "{token}"),
+        }
+    }
+}
+
+/// Returns a stringified version of a slice of `Fragment`.
+pub(crate) fn stringify(fragments: &[Fragment]) -> String {
+    fragments
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Returns `true` if the `Token` bounds the operands of an expression.
+fn is_boundary(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::GroupSeparator | Token::PipeInOperator | Token::PipeOutOperator
+    )
+}
+
+/// Returns `true` if a number literal of a segment of `Fragment` without any
+/// boundary isn't the whole operand of a binary expression.
+fn has_misplaced_literal(segment: &[Fragment]) -> bool {
+    let has_operator = segment
+        .iter()
+        .any(|fragment| matches!(fragment, Fragment::Operator(_)));
+
+    segment
+        .split(|fragment| matches!(fragment, Fragment::Operator(_)))
+        .any(|operand| {
+            operand
+                .iter()
+                .any(|fragment| matches!(fragment, Fragment::Token(Token::NumberLiteral(_))))
+                && (!has_operator
+                    || operand
+                        .iter()
+                        .filter(|fragment| {
+                            !matches!(fragment, Fragment::Token(Token::TruncateOperator))
+                        })
+                        .count()
+                        > 1)
+        })
+}
+
+/// Builds the expression of a segment of `Fragment` without any boundary.
+/// Returns the tokens of the segment as is if there is no binary operator or
+/// `None` if an operand is missing.
+fn build_segment<'a>(segment: &[Fragment<'a>]) -> Option<Vec<Token<'a>>> {
+    let to_tokens = |fragments: &[Fragment<'a>]| -> Vec<Token<'a>> {
+        fragments
+            .iter()
+            .filter_map(|fragment| match fragment {
+                Fragment::Token(token) => Some(token.clone()),
+                Fragment::Operator(_) => None,
+            })
+            .collect()
+    };
+    let mut operators = segment.iter().filter_map(|fragment| match fragment {
+        Fragment::Operator(operator) => Some(*operator),
+        Fragment::Token(_) => None,
+    });
+    let Some(first_operator) = operators.next() else {
+        return Some(to_tokens(segment));
+    };
+
+    let mut operands = segment
+        .split(|fragment| matches!(fragment, Fragment::Operator(_)))
+        .map(to_tokens);
+
+    if operands.clone().any(|operand| operand.is_empty()) {
+        return None;
+    }
+
+    // Operator-precedence parsing, all the operators are left-associative.
+    let mut output: Vec<Expression> = vec![Expression::Operand(operands.next()?)];
+    let mut stack: Vec<BinaryOperator> = vec![];
+
+    let reduce = |output: &mut Vec<Expression<'a>>, operator: BinaryOperator| {
+        // The output always holds one more expression than the stack.
+        let right = output.pop().unwrap();
+        let left = output.pop().unwrap();
+
+        output.push(Expression::binary(left, operator, right));
+    };
+
+    for (operator, operand) in std::iter::once(first_operator)
+        .chain(operators)
+        .zip(operands)
+    {
+        while stack
+            .last()
+            .is_some_and(|top| top.precedence() >= operator.precedence())
+        {
+            // We can safely unwrap here since the stack is not empty.
+            reduce(&mut output, stack.pop().unwrap());
+        }
+
+        stack.push(operator);
+        output.push(Expression::Operand(operand));
+    }
+
+    while let Some(operator) = stack.pop() {
+        reduce(&mut output, operator);
+    }
+
+    output
+        .pop()
+        .map(|expression| vec![Token::Expression(expression)])
+}
+
+/// Builds the expressions out of a slice of `Fragment` and returns a vector of
+/// `Token`.
+/// The operands of an expression are the sub-queries found between the
+/// boundaries, namely the group separator and the pipe operators.
+///
+/// # Errors
+///
+/// Returns a `KjqlParserError` if an operand is missing or if a number
+/// literal isn't the whole operand of a binary expression.
+pub(crate) fn build<'a>(fragments: &[Fragment<'a>]) -> Result<Vec<Token<'a>>, KjqlParserError> {
+    let build_checked_segment = |segment: &[Fragment<'a>]| {
+        if has_misplaced_literal(segment) {
+            return Err(KjqlParserError::LiteralError(stringify(fragments)));
+        }
+
+        build_segment(segment).ok_or_else(|| KjqlParserError::ExpressionError(stringify(fragments)))
+    };
+    let mut tokens = Vec::with_capacity(fragments.len());
+    let mut start = 0;
+
+    for (index, fragment) in fragments.iter().enumerate() {
+        if let Fragment::Token(token) = fragment {
+            if is_boundary(token) {
+                tokens.extend(build_checked_segment(&fragments[start..index])?);
+                tokens.push(token.clone());
+                start = index + 1;
+            }
+        }
+    }
+
+    tokens.extend(build_checked_segment(&fragments[start..])?);
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Fragment,
+        build,
+        stringify,
+    };
+    use crate::{
+        errors::KjqlParserError,
+        tokens::{
+            BinaryOperator,
+            Expression,
+            Number,
+            Token,
+        },
+    };
+
+    #[test]
+    fn check_build_without_operator() {
+        assert_eq!(
+            build(&[
+                Fragment::Token(Token::KeySelector("a")),
+                Fragment::Token(Token::GroupSeparator),
+                Fragment::Token(Token::KeySelector("b")),
+            ]),
+            Ok(vec![
+                Token::KeySelector("a"),
+                Token::GroupSeparator,
+                Token::KeySelector("b"),
+            ])
+        );
+        assert_eq!(build(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn check_build_precedence() {
+        let a = || Expression::Operand(vec![Token::KeySelector("a")]);
+        let b = || Expression::Operand(vec![Token::KeySelector("b"), Token::KeySelector("c")]);
+        let two = || Expression::Operand(vec![Token::NumberLiteral(Number::Integer(2))]);
+
+        assert_eq!(
+            build(&[
+                Fragment::Token(Token::KeySelector("a")),
+                Fragment::Operator(BinaryOperator::Add),
+                Fragment::Token(Token::KeySelector("b")),
+                Fragment::Token(Token::KeySelector("c")),
+                Fragment::Operator(BinaryOperator::Multiply),
+                Fragment::Token(Token::NumberLiteral(Number::Integer(2))),
+                Fragment::Operator(BinaryOperator::Subtract),
+                Fragment::Token(Token::KeySelector("a")),
+            ]),
+            Ok(vec![Token::Expression(Expression::binary(
+                Expression::binary(
+                    a(),
+                    BinaryOperator::Add,
+                    Expression::binary(b(), BinaryOperator::Multiply, two())
+                ),
+                BinaryOperator::Subtract,
+                a()
+            ))])
+        );
+    }
+
+    #[test]
+    fn check_build_with_boundaries() {
+        assert_eq!(
+            build(&[
+                Fragment::Token(Token::KeySelector("a")),
+                Fragment::Token(Token::PipeInOperator),
+                Fragment::Token(Token::KeySelector("b")),
+                Fragment::Operator(BinaryOperator::Divide),
+                Fragment::Token(Token::NumberLiteral(Number::Integer(2))),
+                Fragment::Token(Token::PipeOutOperator),
+                Fragment::Token(Token::KeySelector("c")),
+            ]),
+            Ok(vec![
+                Token::KeySelector("a"),
+                Token::PipeInOperator,
+                Token::Expression(Expression::binary(
+                    Expression::Operand(vec![Token::KeySelector("b")]),
+                    BinaryOperator::Divide,
+                    Expression::Operand(vec![Token::NumberLiteral(Number::Integer(2))]),
+                )),
+                Token::PipeOutOperator,
+                Token::KeySelector("c"),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_build_missing_operand() {
+        let fragments = [
+            Fragment::Token(Token::KeySelector("a")),
+            Fragment::Operator(BinaryOperator::Remainder),
+            Fragment::Token(Token::GroupSeparator),
+        ];
+
+        assert_eq!(
+            build(&fragments),
+            Err(KjqlParserError::ExpressionError(stringify(&fragments)))
+        );
+
+        let fragments = [
+            Fragment::Operator(BinaryOperator::Subtract),
+            Fragment::Token(Token::NumberLiteral(Number::Integer(1))),
+        ];
+
+        assert_eq!(
+            build(&fragments),
+            Err(KjqlParserError::ExpressionError(stringify(&fragments)))
+        );
+    }
+
+    #[test]
+    fn check_build_misplaced_literal() {
+        let fragments = [Fragment::Token(Token::NumberLiteral(Number::Integer(5)))];
+
+        assert_eq!(
+            build(&fragments),
+            Err(KjqlParserError::LiteralError(stringify(&fragments)))
+        );
+
+        let fragments = [
+            Fragment::Token(Token::KeySelector("a")),
+            Fragment::Token(Token::NumberLiteral(Number::Integer(5))),
+            Fragment::Operator(BinaryOperator::Add),
+            Fragment::Token(Token::KeySelector("b")),
+        ];
+
+        assert_eq!(
+            build(&fragments),
+            Err(KjqlParserError::LiteralError(stringify(&fragments)))
+        );
+    }
+
+    #[test]
+    fn check_stringify() {
+        assert_eq!(
+            stringify(&[
+                Fragment::Token(Token::KeySelector("a")),
+                Fragment::Operator(BinaryOperator::Add),
+            ]),
+            r#"KeySelector "a, BinaryOperator (+)"#
+        );
+    }
+}
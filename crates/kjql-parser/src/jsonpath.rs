@@ -0,0 +1,616 @@
+use crate::{
+    errors::KjqlParserError,
+    tokens::{
+        Index,
+        Lens,
+        LensValue,
+        PathSegment,
+        PathSelector,
+        Token,
+    },
+};
+
+/// Root identifier.
+static ROOT: &str = "$";
+/// Current node identifier used in filters.
+static CURRENT: &str = "@";
+/// Descendant segment.
+static DESCENDANT: &str = "..";
+/// Logical OR used in filters.
+static OR: &str = "||";
+/// Equality used in filters.
+static EQUAL: &str = "==";
+
+/// Hand written JSONPath parser keeping track of the remaining input.
+struct PathParser<'a> {
+    /// Whole JSONPath, used in the errors.
+    path: &'a str,
+    /// Remaining input.
+    rest: &'a str,
+}
+
+impl<'a> PathParser<'a> {
+    /// Creates a new `PathParser`.
+    fn new(path: &'a str) -> PathParser<'a> {
+        PathParser { path, rest: path }
+    }
+
+    /// Returns a `KjqlParserError` for the JSONPath.
+    fn error(&self, reason: &str) -> KjqlParserError {
+        KjqlParserError::JsonPathError {
+            path: self.path.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Skips the leading whitespaces of the remaining input.
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes the prefix if the remaining input starts with it.
+    fn eat(&mut self, prefix: &str) -> bool {
+        match self.rest.strip_prefix(prefix) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consumes the prefix or returns an error.
+    fn expect(&mut self, prefix: &str) -> Result<(), KjqlParserError> {
+        if self.eat(prefix) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {prefix}")))
+        }
+    }
+
+    /// Consumes the leading characters matching the predicate and returns them.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let end = self
+            .rest
+            .find(|char: char| !predicate(char))
+            .unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(end);
+
+        self.rest = rest;
+
+        taken
+    }
+
+    /// Parses a member name used after a dot, i.e. `a` in `.a`.
+    fn parse_name(&mut self) -> Option<&'a str> {
+        if !self
+            .rest
+            .starts_with(|char: char| char.is_alphabetic() || char == '_')
+        {
+            return None;
+        }
+
+        Some(self.take_while(|char| char.is_alphanumeric() || char == '_'))
+    }
+
+    /// Parses a single or double quoted string, i.e. `'a'` or `"a"`.
+    fn parse_quoted(&mut self) -> Result<Option<&'a str>, KjqlParserError> {
+        let Some(quote) = self
+            .rest
+            .chars()
+            .next()
+            .filter(|char| ['\'', '"'].contains(char))
+        else {
+            return Ok(None);
+        };
+
+        self.rest = &self.rest[1..];
+
+        let content = self.take_while(|char| char != quote && char != '\\');
+
+        if self.rest.starts_with('\\') {
+            return Err(self.error("escape sequences are not supported"));
+        }
+
+        if !self.eat(&quote.to_string()) {
+            return Err(self.error("unterminated string"));
+        }
+
+        Ok(Some(content))
+    }
+
+    /// Parses an integer, rejecting the negative ones.
+    fn parse_index(&mut self) -> Result<Option<usize>, KjqlParserError> {
+        if self.rest.starts_with('-') {
+            return Err(self.error("negative indexes are not supported"));
+        }
+
+        let digits = self.take_while(|char| char.is_ascii_digit());
+
+        if digits.is_empty() {
+            return Ok(None);
+        }
+
+        digits
+            .parse::<usize>()
+            .map(Some)
+            .map_err(|_| self.error("index too large"))
+    }
+
+    /// Parses the segments of the JSONPath following the root identifier.
+    fn parse_segments(&mut self) -> Result<Vec<PathSegment<'a>>, KjqlParserError> {
+        let mut segments = Vec::new();
+
+        self.skip_whitespace();
+
+        while !self.rest.is_empty() {
+            segments.push(self.parse_segment()?);
+            self.skip_whitespace();
+        }
+
+        Ok(segments)
+    }
+
+    /// Parses a child or a descendant segment.
+    fn parse_segment(&mut self) -> Result<PathSegment<'a>, KjqlParserError> {
+        let descendant = self.eat(DESCENDANT);
+
+        if descendant || self.eat(".") {
+            if self.eat("*") {
+                return Ok(PathSegment(descendant, PathSelector::Wildcard));
+            }
+
+            if let Some(name) = self.parse_name() {
+                return Ok(PathSegment(descendant, PathSelector::Keys(vec![name])));
+            }
+
+            if !descendant || !self.rest.starts_with('[') {
+                return Err(self.error("expected a member name or * after ."));
+            }
+        }
+
+        if self.eat("[") {
+            return Ok(PathSegment(descendant, self.parse_bracket()?));
+        }
+
+        Err(self.error(&format!("unexpected input {}", self.rest)))
+    }
+
+    /// Parses the selectors surrounded by square brackets, the opening one
+    /// being already consumed.
+    fn parse_bracket(&mut self) -> Result<PathSelector<'a>, KjqlParserError> {
+        self.skip_whitespace();
+
+        let selector = if self.eat("?") {
+            PathSelector::Filter(self.parse_filter()?)
+        } else if self.eat("*") {
+            PathSelector::Wildcard
+        } else if let Some(key) = self.parse_quoted()? {
+            let mut keys = vec![key];
+
+            while self.eat_separator() {
+                match self.parse_quoted()? {
+                    Some(key) => keys.push(key),
+                    None => return Err(self.error("names and indexes can't be mixed")),
+                }
+            }
+
+            PathSelector::Keys(keys)
+        } else {
+            self.parse_indexes_or_slice()?
+        };
+
+        self.skip_whitespace();
+        self.expect("]")?;
+
+        Ok(selector)
+    }
+
+    /// Consumes a comma surrounded by optional whitespaces.
+    fn eat_separator(&mut self) -> bool {
+        self.skip_whitespace();
+
+        let found = self.eat(",");
+
+        self.skip_whitespace();
+
+        found
+    }
+
+    /// Parses a list of indexes or a single slice.
+    fn parse_indexes_or_slice(&mut self) -> Result<PathSelector<'a>, KjqlParserError> {
+        let start = self.parse_index()?;
+
+        self.skip_whitespace();
+
+        if self.eat(":") {
+            return self.parse_slice(start);
+        }
+
+        let Some(start) = start else {
+            return Err(self.error(&format!("unexpected input {}", self.rest)));
+        };
+        let mut indexes = vec![Index(start)];
+
+        while self.eat_separator() {
+            match self.parse_index()? {
+                Some(index) => indexes.push(Index(index)),
+                None if self.rest.starts_with(['\'', '"']) => {
+                    return Err(self.error("names and indexes can't be mixed"));
+                }
+                None => return Err(self.error("expected an index")),
+            }
+
+            self.skip_whitespace();
+
+            if self.rest.starts_with(':') {
+                return Err(self.error("slices can't be combined with other selectors"));
+            }
+        }
+
+        Ok(PathSelector::Indexes(indexes))
+    }
+
+    /// Parses the end and the step of a slice, its start and the colon being
+    /// already consumed.
+    fn parse_slice(&mut self, start: Option<usize>) -> Result<PathSelector<'a>, KjqlParserError> {
+        self.skip_whitespace();
+
+        let end = self.parse_index()?;
+
+        self.skip_whitespace();
+
+        if self.eat(":") {
+            self.skip_whitespace();
+
+            if let Some(step) = self.parse_index()? {
+                if step != 1 {
+                    return Err(self.error("slice steps other than 1 are not supported"));
+                }
+            }
+        }
+
+        Ok(PathSelector::Slice(
+            Index(start.unwrap_or(0)),
+            end.map(Index),
+        ))
+    }
+
+    /// Parses a filter expression made of comparisons separated by `||`, the
+    /// question mark being already consumed.
+    fn parse_filter(&mut self) -> Result<Vec<Lens<'a>>, KjqlParserError> {
+        self.skip_whitespace();
+
+        let parenthesized = self.eat("(");
+        let mut lenses = vec![self.parse_comparison()?];
+
+        self.skip_whitespace();
+
+        while self.eat(OR) {
+            lenses.push(self.parse_comparison()?);
+            self.skip_whitespace();
+        }
+
+        if parenthesized {
+            self.expect(")")?;
+        }
+
+        if !self.rest.starts_with(']') {
+            return Err(self.error("only == and || are supported in filters"));
+        }
+
+        Ok(lenses)
+    }
+
+    /// Parses a relative path existence test or its equality with a literal.
+    fn parse_comparison(&mut self) -> Result<Lens<'a>, KjqlParserError> {
+        self.skip_whitespace();
+
+        if !self.eat(CURRENT) {
+            return Err(self.error("filters must start with @"));
+        }
+
+        let tokens = self.parse_relative_path()?;
+
+        self.skip_whitespace();
+
+        let value = if self.eat(EQUAL) {
+            self.skip_whitespace();
+            self.parse_literal()?
+        } else {
+            LensValue::Exists
+        };
+
+        Ok(Lens::new(&tokens, Some(value)))
+    }
+
+    /// Parses the member names and indexes following `@`.
+    fn parse_relative_path(&mut self) -> Result<Vec<Token<'a>>, KjqlParserError> {
+        let mut tokens = Vec::new();
+
+        loop {
+            if self.rest.starts_with(DESCENDANT) {
+                return Err(self.error("descendant segments are not supported in filters"));
+            }
+
+            if self.eat(".") {
+                match self.parse_name() {
+                    Some(name) => tokens.push(Token::KeySelector(name)),
+                    None => return Err(self.error("expected a member name after @.")),
+                }
+            } else if self.eat("[") {
+                self.skip_whitespace();
+
+                if let Some(key) = self.parse_quoted()? {
+                    tokens.push(Token::KeySelector(key));
+                } else if let Some(index) = self.parse_index()? {
+                    tokens.push(Token::ArrayIndexSelector(vec![Index(index)]));
+                } else {
+                    return Err(self.error("only names and indexes are supported in filters"));
+                }
+
+                self.skip_whitespace();
+                self.expect("]")?;
+            } else {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    /// Parses a literal compared in a filter.
+    fn parse_literal(&mut self) -> Result<LensValue<'a>, KjqlParserError> {
+        if let Some(string) = self.parse_quoted()? {
+            return Ok(LensValue::String(string));
+        }
+
+        for (keyword, value) in [
+            ("true", LensValue::Bool(true)),
+            ("false", LensValue::Bool(false)),
+            ("null", LensValue::Null),
+        ] {
+            if self.eat(keyword) {
+                return Ok(value);
+            }
+        }
+
+        match self.parse_index() {
+            Ok(Some(number)) if !self.rest.starts_with(['.', 'e', 'E']) => {
+                Ok(LensValue::Number(number))
+            }
+            _ => Err(self
+                .error("only strings, non-negative integers, booleans and null can be compared")),
+        }
+    }
+}
+
+/// Parses a JSONPath like `$.spec.containers[?(@.name == 'app')].image` and
+/// returns a `JsonPathSelector` token selecting its nodelist.
+/// The common subset is supported: the root `$`, member names with `.a` or
+/// `['a']`, indexes, slices without steps, wildcards, descendants with `..`
+/// and filters made of `@` relative paths, `==` and `||`.
+///
+/// # Errors
+///
+/// Returns a `KjqlParserError` if the input is not a JSONPath or if it uses an
+/// unsupported construct.
+pub fn parse_jsonpath(input: &str) -> Result<Vec<Token<'_>>, KjqlParserError> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(KjqlParserError::EmptyInputError);
+    }
+
+    let mut parser = PathParser::new(input);
+
+    if !parser.eat(ROOT) {
+        return Err(parser.error("a JSONPath must start with $"));
+    }
+
+    Ok(vec![Token::JsonPathSelector(parser.parse_segments()?)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_jsonpath;
+    use crate::{
+        errors::KjqlParserError,
+        tokens::{
+            Index,
+            Lens,
+            LensValue,
+            PathSegment,
+            PathSelector,
+            Token,
+        },
+    };
+
+    fn jsonpath_error(path: &str, reason: &str) -> Result<Vec<Token<'static>>, KjqlParserError> {
+        Err(KjqlParserError::JsonPathError {
+            path: path.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+
+    fn path(segments: Vec<PathSegment<'_>>) -> Result<Vec<Token<'_>>, KjqlParserError> {
+        Ok(vec![Token::JsonPathSelector(segments)])
+    }
+
+    fn child(selector: PathSelector<'_>) -> PathSegment<'_> {
+        PathSegment::new(false, selector)
+    }
+
+    fn descendant(selector: PathSelector<'_>) -> PathSegment<'_> {
+        PathSegment::new(true, selector)
+    }
+
+    fn key(key: &str) -> PathSelector<'_> {
+        PathSelector::Keys(vec![key])
+    }
+
+    #[test]
+    fn check_parse_jsonpath_children() {
+        assert_eq!(parse_jsonpath("$"), path(vec![]));
+        assert_eq!(
+            parse_jsonpath("$.spec['containers'][0][\"image\"]"),
+            path(vec![
+                child(key("spec")),
+                child(key("containers")),
+                child(PathSelector::Indexes(vec![Index(0)])),
+                child(key("image")),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[1:3].b"),
+            path(vec![
+                child(key("a")),
+                child(PathSelector::Slice(Index(1), Some(Index(3)))),
+                child(key("b")),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[:2]"),
+            path(vec![
+                child(key("a")),
+                child(PathSelector::Slice(Index(0), Some(Index(2)))),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[1::1]"),
+            path(vec![
+                child(key("a")),
+                child(PathSelector::Slice(Index(1), None)),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[0, 2]"),
+            path(vec![
+                child(key("a")),
+                child(PathSelector::Indexes(vec![Index(0), Index(2)])),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$['a','b'][0]"),
+            path(vec![
+                child(PathSelector::Keys(vec!["a", "b"])),
+                child(PathSelector::Indexes(vec![Index(0)])),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[*].*"),
+            path(vec![
+                child(key("a")),
+                child(PathSelector::Wildcard),
+                child(PathSelector::Wildcard),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_parse_jsonpath_filters() {
+        assert_eq!(
+            parse_jsonpath("$.a[?(@.b == 'x' || @['c'][0] == 1)].d"),
+            path(vec![
+                child(key("a")),
+                child(PathSelector::Filter(vec![
+                    Lens::new(&[Token::KeySelector("b")], Some(LensValue::String("x"))),
+                    Lens::new(
+                        &[
+                            Token::KeySelector("c"),
+                            Token::ArrayIndexSelector(vec![Index(0)])
+                        ],
+                        Some(LensValue::Number(1))
+                    ),
+                ])),
+                child(key("d")),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$[?@ == true]"),
+            path(vec![child(PathSelector::Filter(vec![Lens::new(
+                &[],
+                Some(LensValue::Bool(true))
+            )]))])
+        );
+    }
+
+    #[test]
+    fn check_parse_jsonpath_descendants() {
+        assert_eq!(
+            parse_jsonpath("$..name"),
+            path(vec![descendant(key("name"))])
+        );
+        assert_eq!(
+            parse_jsonpath("$.a..[0]"),
+            path(vec![
+                child(key("a")),
+                descendant(PathSelector::Indexes(vec![Index(0)])),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$..*"),
+            path(vec![descendant(PathSelector::Wildcard)])
+        );
+        assert_eq!(
+            parse_jsonpath("$..book[?(@.isbn)].title"),
+            path(vec![
+                descendant(key("book")),
+                child(PathSelector::Filter(vec![Lens::new(
+                    &[Token::KeySelector("isbn")],
+                    Some(LensValue::Exists)
+                )])),
+                child(key("title")),
+            ])
+        );
+        assert_eq!(
+            parse_jsonpath("$.*..['a','b']"),
+            path(vec![
+                child(PathSelector::Wildcard),
+                descendant(PathSelector::Keys(vec!["a", "b"])),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_parse_jsonpath_errors() {
+        assert_eq!(parse_jsonpath(" "), Err(KjqlParserError::EmptyInputError));
+        assert_eq!(
+            parse_jsonpath(".a"),
+            jsonpath_error(".a", "a JSONPath must start with $")
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[-1]"),
+            jsonpath_error("$.a[-1]", "negative indexes are not supported")
+        );
+        assert_eq!(
+            parse_jsonpath("$.a[0:4:2]"),
+            jsonpath_error("$.a[0:4:2]", "slice steps other than 1 are not supported")
+        );
+        assert_eq!(
+            parse_jsonpath("$[?@.a && @.b]"),
+            jsonpath_error("$[?@.a && @.b]", "only == and || are supported in filters")
+        );
+        assert_eq!(
+            parse_jsonpath("$[?@.a == 1.5]"),
+            jsonpath_error(
+                "$[?@.a == 1.5]",
+                "only strings, non-negative integers, booleans and null can be compared"
+            )
+        );
+        assert_eq!(
+            parse_jsonpath(r"$['a\'b']"),
+            jsonpath_error(r"$['a\'b']", "escape sequences are not supported")
+        );
+        assert_eq!(
+            parse_jsonpath("$['a', 0]"),
+            jsonpath_error("$['a', 0]", "names and indexes can't be mixed")
+        );
+        assert_eq!(
+            parse_jsonpath("$.a b"),
+            jsonpath_error("$.a b", "unexpected input b")
+        );
+        assert_eq!(
+            parse_jsonpath("$['a'"),
+            jsonpath_error("$['a'", "expected ]")
+        );
+    }
+}
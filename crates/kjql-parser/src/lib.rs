@@ -4,7 +4,20 @@
 #![doc = include_str!("../README.md")]
 
 mod combinators;
+/// Dotted path parser.
+pub mod dotted;
+/// Parser errors.
 pub mod errors;
+mod expression;
+/// Group splitter.
 pub mod group;
+/// JSONPath parser.
+pub mod jsonpath;
+/// Parser.
 pub mod parser;
+/// JSON Pointer parser.
+pub mod pointer;
+/// Query serializer.
+pub mod serializer;
+/// Parser tokens.
 pub mod tokens;
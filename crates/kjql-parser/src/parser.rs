@@ -1,12 +1,17 @@
 use winnow::{
-    PResult,
     Parser,
     combinator::{
         alt,
         dispatch,
         fail,
-        iterator,
         peek,
+        repeat,
+    },
+    error::{
+        ErrMode,
+        ParserError,
+        StrContext,
+        StrContextValue,
     },
     token::any,
 };
@@ -14,26 +19,64 @@ use winnow::{
 use crate::{
     combinators::{
         parse_array_index,
+        parse_array_mixed,
         parse_array_range,
+        parse_average_operator,
+        parse_binary_operator,
+        parse_conditional,
+        parse_conversion_or_trim_operator,
+        parse_descendants_operator,
+        parse_first_operator,
         parse_flatten_operator,
+        parse_from_json_operator,
+        parse_group_by_operator,
         parse_group_separator,
+        parse_has_operator,
+        parse_join_operator,
+        parse_json_type,
         parse_key,
         parse_keys_operator,
+        parse_last_operator,
         parse_lenses,
+        parse_limit_operator,
+        parse_lower_operator,
+        parse_merge_operator,
+        parse_min_max_operator,
         parse_multi_key,
+        parse_number_literal,
         parse_object_index,
+        parse_object_key_range,
+        parse_object_mixed,
         parse_object_range,
         parse_pipe_in_operator,
         parse_pipe_out_operator,
+        parse_replace_operator,
+        parse_skip_operator,
+        parse_sort_operator,
+        parse_split_operator,
+        parse_sum_operator,
+        parse_transpose_operator,
         parse_truncate_operator,
+        parse_type_operator,
+        parse_unique_operator,
+        parse_upper_operator,
+        parse_values_operator,
+        parse_zip_operator,
         trim,
     },
-    errors::KjqlParserError,
+    errors::{
+        KjqlParserError,
+        PResult,
+    },
+    expression::{
+        Fragment,
+        build,
+        stringify,
+    },
     tokens::{
         Lens,
         Range,
         Token,
-        View,
     },
 };
 
@@ -45,6 +88,7 @@ fn parse_fragment<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
                     alt((
                         parse_array_index.map(Token::ArrayIndexSelector),
                         parse_array_range.map(|(start, end)| Token::ArrayRangeSelector(Range(start, end))),
+                        parse_array_mixed.map(Token::ArrayMixedSelector),
                     ))
                 },
             '"' => parse_key.map(Token::KeySelector),
@@ -53,6 +97,8 @@ fn parse_fragment<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
                     parse_multi_key.map(Token::MultiKeySelector),
                     parse_object_index.map(Token::ObjectIndexSelector),
                     parse_object_range.map(|(start, end)| Token::ObjectRangeSelector(Range(start, end))),
+                    parse_object_key_range.map(Token::ObjectKeyRangeSelector),
+                    parse_object_mixed.map(Token::ObjectMixedSelector),
                 ))
             },
             '|' => {
@@ -70,48 +116,132 @@ fn parse_fragment<'a>(input: &mut &'a str) -> PResult<Token<'a>> {
             },
             '@' => parse_keys_operator.value(Token::KeyOperator),
             '.' => parse_flatten_operator.value(Token::FlattenOperator),
-            '<' => parse_pipe_out_operator.value(Token::PipeOutOperator),
+            '<' => {
+                alt((
+                    parse_pipe_out_operator.value(Token::PipeOutOperator),
+                    parse_json_type.map(Token::TypeSelector),
+                ))
+            },
             ',' => parse_group_separator.value(Token::GroupSeparator),
             '!' => parse_truncate_operator.value(Token::TruncateOperator),
+            '0'..='9' => parse_number_literal.map(Token::NumberLiteral),
+            'a' => parse_average_operator.value(Token::AverageOperator),
+            'd' => parse_descendants_operator.value(Token::DescendantsOperator),
+            'f' => {
+                alt((
+                    parse_first_operator.value(Token::FirstOperator),
+                    parse_from_json_operator.value(Token::FromJsonOperator),
+                ))
+            },
+            'g' => parse_group_by_operator.map(Token::GroupByOperator),
+            'h' => parse_has_operator,
+            'i' => parse_conditional(parse_branch),
+            'j' => parse_join_operator.map(Token::JoinOperator),
+            'l' => {
+                alt((
+                    parse_last_operator.value(Token::LastOperator),
+                    parse_limit_operator.map(Token::LimitOperator),
+                    parse_lower_operator.value(Token::LowerOperator),
+                ))
+            },
+            'm' => alt((parse_min_max_operator, parse_merge_operator)),
+            'r' => parse_replace_operator.map(|(from, to)| Token::ReplaceOperator(from, to)),
+            's' => {
+                alt((
+                    parse_skip_operator.map(Token::SkipOperator),
+                    parse_sort_operator,
+                    parse_split_operator.map(Token::SplitOperator),
+                    parse_sum_operator.value(Token::SumOperator),
+                ))
+            },
+            't' => {
+                alt((
+                    parse_conversion_or_trim_operator,
+                    parse_transpose_operator.map(Token::TransposeOperator),
+                    parse_type_operator.value(Token::TypeOperator),
+                ))
+            },
+            'u' => {
+                alt((
+                    parse_unique_operator,
+                    parse_upper_operator.value(Token::UpperOperator),
+                ))
+            },
+            'v' => parse_values_operator.value(Token::ValuesOperator),
+            'z' => parse_zip_operator,
             _ => fail
         }
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a selector or an operator",
+        ))),
     )
         .parse_next(input)
 }
 
+/// Parses the provided input and map it to the first matching token or
+/// binary operator.
+fn parse_fragment_or_operator<'a>(input: &mut &'a str) -> PResult<Fragment<'a>> {
+    let start = *input;
+    let error = match parse_fragment.parse_next(input) {
+        Ok(token) => return Ok(Fragment::Token(token)),
+        Err(ErrMode::Backtrack(error)) => error,
+        Err(error) => return Err(error),
+    };
+
+    *input = start;
+    match parse_binary_operator.parse_next(input) {
+        // A fragment failing past the operator, like lenses being parsed as a
+        // union, can't be followed by a valid fragment.
+        Ok(_) if error.offset(start) > start.len() - input.len() => Err(ErrMode::Backtrack(error)),
+        Ok(operator) => Ok(Fragment::Operator(operator)),
+        Err(other) => Err(other.map(|other| error.or(other))),
+    }
+}
+
+/// Parses a conditional branch and returns a vector of `Tokens`.
+fn parse_branch<'a>(input: &mut &'a str) -> PResult<Vec<Token<'a>>> {
+    repeat(1.., parse_fragment_or_operator)
+        .try_map(|fragments: Vec<Fragment<'a>>| build(&fragments))
+        .parse_next(input)
+}
+
 /// Parses the provided input and returns a vector of `Tokens`.
 ///
 /// # Errors
 ///
 /// Returns a `KjqlParserError` if the input cannot be parsed.
-pub fn parse(input: &str) -> Result<Vec<Token>, KjqlParserError> {
-    let mut parser_iterator = iterator(input, parse_fragment);
-    let tokens = parser_iterator.collect::<Vec<Token>>();
-    let result: PResult<_, _> = parser_iterator.finish();
-
-    match result {
-        Ok((unparsed, ())) => {
-            if !unparsed.is_empty() {
+pub fn parse(input: &str) -> Result<Vec<Token<'_>>, KjqlParserError> {
+    let mut fragments = Vec::new();
+    let mut unparsed = input;
+
+    while !unparsed.is_empty() {
+        let start = unparsed;
+
+        match parse_fragment_or_operator.parse_next(&mut unparsed) {
+            Ok(fragment) => fragments.push(fragment),
+            Err(error) => {
+                let error = error.into_inner().unwrap_or_default();
+
                 return Err(KjqlParserError::ParsingError {
-                    tokens: tokens.stringify(),
-                    unparsed: unparsed.to_string(),
+                    tokens: stringify(&fragments),
+                    unparsed: start.to_string(),
+                    offset: error.offset(input),
+                    expected: error.expected(),
                 });
             }
-
-            let trucate_count = tokens
-                .iter()
-                .filter(|&token| *token == Token::TruncateOperator)
-                .count();
-            if trucate_count > 1
-                || (trucate_count == 1 && tokens.last() != Some(&Token::TruncateOperator))
-            {
-                return Err(KjqlParserError::TruncateError(tokens.stringify()));
-            }
-
-            Ok(tokens)
         }
-        Err(_) => Err(KjqlParserError::UnknownError),
     }
+
+    let truncate = Fragment::Token(Token::TruncateOperator);
+    let trucate_count = fragments
+        .iter()
+        .filter(|&fragment| *fragment == truncate)
+        .count();
+    if trucate_count > 1 || (trucate_count == 1 && fragments.last() != Some(&truncate)) {
+        return Err(KjqlParserError::TruncateError(stringify(&fragments)));
+    }
+
+    build(&fragments)
 }
 
 #[cfg(test)]
@@ -123,10 +253,19 @@ mod tests {
     use crate::{
         errors::KjqlParserError,
         tokens::{
+            Alignment,
+            BinaryOperator,
+            Expression,
             Index,
+            JsonType,
+            KeyRange,
             Lens,
             LensValue,
+            MergeStrategy,
+            Number,
+            Order,
             Range,
+            Selection,
             Token,
             View,
         },
@@ -245,6 +384,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_mixed_selectors() {
+        assert_eq!(
+            Ok(Token::ArrayMixedSelector(vec![
+                Selection::Index(Index(0)),
+                Selection::Range(Range(Some(Index(3)), Some(Index(6)))),
+            ])),
+            parse_fragment(&mut "[0, 3:6]"),
+        );
+        assert_eq!(
+            Ok(Token::ObjectMixedSelector(vec![
+                Selection::Index(Index(0)),
+                Selection::Range(Range(Some(Index(2)), Some(Index(4)))),
+            ])),
+            parse_fragment(&mut "{0, 2:4}"),
+        );
+        assert_eq!(
+            Ok(Token::ArrayIndexSelector(vec![Index(0), Index(2)])),
+            parse_fragment(&mut "[0,2]"),
+        );
+        assert_eq!(
+            Ok(Token::ArrayMixedSelector(vec![
+                Selection::Index(Index(0)),
+                Selection::Range(Range(Some(Index(3)), Some(Index(6)))),
+                Selection::IndexFromEnd(Index(1)),
+            ])),
+            parse_fragment(&mut "[0, 3:6, -1]"),
+        );
+        assert_eq!(
+            Ok(Token::ArrayMixedSelector(vec![Selection::IndexFromEnd(
+                Index(1)
+            )])),
+            parse_fragment(&mut "[-1]"),
+        );
+    }
+
+    #[test]
+    fn check_object_key_range_selector() {
+        assert_eq!(
+            Ok(Token::ObjectKeyRangeSelector(KeyRange("b", "f", true))),
+            parse_fragment(&mut r#"{"b":"f"}"#),
+        );
+        assert_eq!(
+            Ok(Token::ObjectKeyRangeSelector(KeyRange("f", "b", false))),
+            parse_fragment(&mut r#" {"f".."b"} "#),
+        );
+    }
+
     #[test]
     fn check_lens_selector() {
         assert_eq!(
@@ -273,6 +460,15 @@ mod tests {
         assert_eq!(parse_fragment(&mut " .. "), Ok(Token::FlattenOperator));
     }
 
+    #[test]
+    fn check_values_and_descendants_operators() {
+        assert_eq!(parse_fragment(&mut "values"), Ok(Token::ValuesOperator));
+        assert_eq!(
+            parse_fragment(&mut " descendants "),
+            Ok(Token::DescendantsOperator)
+        );
+    }
+
     #[test]
     fn check_pipe_in_operator() {
         assert_eq!(parse_fragment(&mut "|>"), Ok(Token::PipeInOperator));
@@ -297,6 +493,404 @@ mod tests {
         assert_eq!(parse_fragment(&mut " , "), Ok(Token::GroupSeparator));
     }
 
+    #[test]
+    fn check_sort_operator() {
+        assert_eq!(
+            parse_fragment(&mut " sort "),
+            Ok(Token::SortOperator(Order::Ascending))
+        );
+        assert_eq!(
+            parse_fragment(&mut "sort_desc"),
+            Ok(Token::SortOperator(Order::Descending))
+        );
+        assert_eq!(
+            parse_fragment(&mut r#"sort_by("meta""created")"#),
+            Ok(Token::SortByOperator(
+                vec![Token::KeySelector("meta"), Token::KeySelector("created")],
+                Order::Ascending
+            ))
+        );
+        assert_eq!(
+            parse_fragment(&mut r#"sort_by_desc("a")"#),
+            Ok(Token::SortByOperator(
+                vec![Token::KeySelector("a")],
+                Order::Descending
+            ))
+        );
+    }
+
+    #[test]
+    fn check_unique_operator() {
+        assert_eq!(parse_fragment(&mut " unique "), Ok(Token::UniqueOperator));
+        assert_eq!(
+            parse_fragment(&mut r#"unique_by("a")"#),
+            Ok(Token::UniqueByOperator(vec![Token::KeySelector("a")]))
+        );
+    }
+
+    #[test]
+    fn check_group_by_operator() {
+        assert_eq!(
+            parse_fragment(&mut r#" group_by("a""b") "#),
+            Ok(Token::GroupByOperator(vec![
+                Token::KeySelector("a"),
+                Token::KeySelector("b")
+            ]))
+        );
+    }
+
+    #[test]
+    fn check_aggregate_operators() {
+        assert_eq!(parse_fragment(&mut " sum "), Ok(Token::SumOperator));
+        assert_eq!(parse_fragment(&mut " avg "), Ok(Token::AverageOperator));
+        assert_eq!(parse_fragment(&mut " min "), Ok(Token::MinOperator));
+        assert_eq!(parse_fragment(&mut " max "), Ok(Token::MaxOperator));
+        assert_eq!(
+            parse_fragment(&mut r#"min_by("a")"#),
+            Ok(Token::MinByOperator(vec![Token::KeySelector("a")]))
+        );
+        assert_eq!(
+            parse_fragment(&mut r#"max_by("a")"#),
+            Ok(Token::MaxByOperator(vec![Token::KeySelector("a")]))
+        );
+    }
+
+    #[test]
+    fn check_number_literal() {
+        assert_eq!(
+            parse_fragment(&mut " 1024 "),
+            Ok(Token::NumberLiteral(Number::Integer(1024)))
+        );
+        assert_eq!(
+            parse_fragment(&mut "0.5"),
+            Ok(Token::NumberLiteral(Number::Float(0.5)))
+        );
+    }
+
+    #[test]
+    fn check_expression() {
+        assert_eq!(
+            parse(r#""items"|>"price" * "qty" + 1<|"#),
+            Ok(vec![
+                Token::KeySelector("items"),
+                Token::PipeInOperator,
+                Token::Expression(Expression::binary(
+                    Expression::binary(
+                        Expression::Operand(vec![Token::KeySelector("price")]),
+                        BinaryOperator::Multiply,
+                        Expression::Operand(vec![Token::KeySelector("qty")]),
+                    ),
+                    BinaryOperator::Add,
+                    Expression::Operand(vec![Token::NumberLiteral(Number::Integer(1))]),
+                )),
+                Token::PipeOutOperator,
+            ])
+        );
+        assert_eq!(
+            parse(r#""a""b"/1024,"c""#),
+            Ok(vec![
+                Token::Expression(Expression::binary(
+                    Expression::Operand(vec![Token::KeySelector("a"), Token::KeySelector("b")]),
+                    BinaryOperator::Divide,
+                    Expression::Operand(vec![Token::NumberLiteral(Number::Integer(1024))]),
+                )),
+                Token::GroupSeparator,
+                Token::KeySelector("c"),
+            ])
+        );
+        assert_eq!(
+            parse(r#""a" *"#),
+            Err(KjqlParserError::ExpressionError(
+                r#"KeySelector "a, BinaryOperator (*)"#.to_string()
+            ))
+        );
+        assert_eq!(
+            parse("5"),
+            Err(KjqlParserError::LiteralError(
+                "NumberLiteral (5)".to_string()
+            ))
+        );
+        assert!(matches!(
+            parse(r#""a",1.5"#),
+            Err(KjqlParserError::LiteralError(_))
+        ));
+        assert!(parse("1 + 2").is_ok());
+    }
+
+    #[test]
+    fn check_string_operators() {
+        assert_eq!(
+            parse_fragment(&mut r#" split(",") "#),
+            Ok(Token::SplitOperator(","))
+        );
+        assert_eq!(
+            parse_fragment(&mut r#"join("-")"#),
+            Ok(Token::JoinOperator("-"))
+        );
+        assert_eq!(parse_fragment(&mut "lower"), Ok(Token::LowerOperator));
+        assert_eq!(parse_fragment(&mut "upper"), Ok(Token::UpperOperator));
+        assert_eq!(parse_fragment(&mut "trim"), Ok(Token::TrimOperator));
+        assert_eq!(
+            parse_fragment(&mut r#"replace("a","b")"#),
+            Ok(Token::ReplaceOperator("a", "b"))
+        );
+    }
+
+    #[test]
+    fn check_conversion_operators() {
+        assert_eq!(
+            parse_fragment(&mut " tostring "),
+            Ok(Token::ToStringOperator)
+        );
+        assert_eq!(parse_fragment(&mut "tonumber"), Ok(Token::ToNumberOperator));
+        assert_eq!(parse_fragment(&mut "tojson"), Ok(Token::ToJsonOperator));
+        assert_eq!(parse_fragment(&mut "fromjson"), Ok(Token::FromJsonOperator));
+    }
+
+    #[test]
+    fn check_type() {
+        assert_eq!(parse_fragment(&mut "type"), Ok(Token::TypeOperator));
+        assert_eq!(
+            parse_fragment(&mut "<number>"),
+            Ok(Token::TypeSelector(JsonType::Number))
+        );
+        assert_eq!(
+            parse(r#""a"|={"id"=<string>}<object>"#),
+            Ok(vec![
+                Token::KeySelector("a"),
+                Token::LensSelector(vec![Lens::new(
+                    &[Token::KeySelector("id")],
+                    Some(LensValue::Type(JsonType::String))
+                )]),
+                Token::TypeSelector(JsonType::Object),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_slice_operators() {
+        assert_eq!(
+            parse(r#""a"skip(1)limit(2)"#),
+            Ok(vec![
+                Token::KeySelector("a"),
+                Token::SkipOperator(1),
+                Token::LimitOperator(2),
+            ])
+        );
+        assert_eq!(parse_fragment(&mut "first"), Ok(Token::FirstOperator));
+        assert_eq!(parse_fragment(&mut "fromjson"), Ok(Token::FromJsonOperator));
+        assert_eq!(parse_fragment(&mut "last"), Ok(Token::LastOperator));
+        assert_eq!(parse_fragment(&mut "lower"), Ok(Token::LowerOperator));
+    }
+
+    #[test]
+    fn check_zip_operators() {
+        assert_eq!(
+            parse_fragment(&mut r#"zip_pad("a","b""c")"#),
+            Ok(Token::ZipOperator(
+                vec![
+                    vec![Token::KeySelector("a")],
+                    vec![Token::KeySelector("b"), Token::KeySelector("c")],
+                ],
+                Alignment::Pad
+            ))
+        );
+        assert_eq!(
+            parse_fragment(&mut "transpose"),
+            Ok(Token::TransposeOperator(Alignment::Strict))
+        );
+        assert_eq!(parse_fragment(&mut "trim"), Ok(Token::TrimOperator));
+    }
+
+    #[test]
+    fn check_merge_operator() {
+        assert_eq!(
+            parse_fragment(&mut r#"merge("a","b""c","d")"#),
+            Ok(Token::MergeOperator(
+                vec![
+                    vec![Token::KeySelector("a")],
+                    vec![Token::KeySelector("b"), Token::KeySelector("c")],
+                    vec![Token::KeySelector("d")],
+                ],
+                MergeStrategy::Replace
+            ))
+        );
+        assert_eq!(
+            parse_fragment(&mut r#"merge_concat("a","b")"#),
+            Ok(Token::MergeOperator(
+                vec![vec![Token::KeySelector("a")], vec![Token::KeySelector("b")]],
+                MergeStrategy::Concatenate
+            ))
+        );
+        assert_eq!(parse_fragment(&mut "max"), Ok(Token::MaxOperator));
+    }
+
+    #[test]
+    fn check_has_operator() {
+        assert_eq!(
+            parse_fragment(&mut r#"has("a")"#),
+            Ok(Token::HasKeyOperator("a"))
+        );
+        assert_eq!(
+            parse_fragment(&mut "has(0)"),
+            Ok(Token::HasIndexOperator(Index(0)))
+        );
+        assert_eq!(
+            parse(r#"if "a"=exists then "a" else "b" end"#),
+            Ok(vec![Token::Conditional(
+                vec![Lens::new(
+                    &[Token::KeySelector("a")],
+                    Some(LensValue::Exists)
+                )],
+                vec![Token::KeySelector("a")],
+                vec![Token::KeySelector("b")],
+            )])
+        );
+    }
+
+    #[test]
+    fn check_conditional() {
+        assert_eq!(
+            parse_fragment(&mut r#"if "a"=1,"b" then "c" else "d" * 2 end"#),
+            Ok(Token::Conditional(
+                vec![
+                    Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(1))),
+                    Lens::new(&[Token::KeySelector("b")], None),
+                ],
+                vec![Token::KeySelector("c")],
+                vec![Token::Expression(Expression::binary(
+                    Expression::Operand(vec![Token::KeySelector("d")]),
+                    BinaryOperator::Multiply,
+                    Expression::Operand(vec![Token::NumberLiteral(Number::Integer(2))]),
+                ))],
+            ))
+        );
+        assert_eq!(
+            parse(r#""a"|>if "b" then if "c" then "c" else "b" end else "a" end<|"#),
+            Ok(vec![
+                Token::KeySelector("a"),
+                Token::PipeInOperator,
+                Token::Conditional(
+                    vec![Lens::new(&[Token::KeySelector("b")], None)],
+                    vec![Token::Conditional(
+                        vec![Lens::new(&[Token::KeySelector("c")], None)],
+                        vec![Token::KeySelector("c")],
+                        vec![Token::KeySelector("b")],
+                    )],
+                    vec![Token::KeySelector("a")],
+                ),
+                Token::PipeOutOperator,
+            ])
+        );
+        assert!(parse(r#"if "a" then "b" end"#).is_err());
+        assert!(parse(r#"if "a" then else "b" end"#).is_err());
+    }
+
+    #[test]
+    fn check_set_expression() {
+        let operand = |key| Expression::Operand(vec![Token::KeySelector(key)]);
+
+        assert_eq!(
+            parse(r#""a" | "b" & "c" - "d""#),
+            Ok(vec![Token::Expression(Expression::binary(
+                operand("a"),
+                BinaryOperator::Union,
+                Expression::binary(
+                    operand("b"),
+                    BinaryOperator::Intersection,
+                    Expression::binary(operand("c"), BinaryOperator::Subtract, operand("d")),
+                ),
+            ))])
+        );
+        assert_eq!(
+            parse(r#""a"|"b"|>"c"<|"#),
+            Ok(vec![
+                Token::Expression(Expression::binary(
+                    operand("a"),
+                    BinaryOperator::Union,
+                    operand("b"),
+                )),
+                Token::PipeInOperator,
+                Token::KeySelector("c"),
+                Token::PipeOutOperator,
+            ])
+        );
+        assert_eq!(
+            parse(r#""a"|={"b"}"#),
+            Ok(vec![
+                Token::KeySelector("a"),
+                Token::LensSelector(vec![Lens::new(&[Token::KeySelector("b")], None)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_parsing_errors() {
+        assert_eq!(
+            parse(r#""a"firstly"#),
+            Err(KjqlParserError::ParsingError {
+                tokens: [Token::KeySelector("a")].stringify(),
+                unparsed: "firstly".to_string(),
+                offset: 3,
+                expected: vec!["a selector or an operator".to_string()],
+            })
+        );
+        assert_eq!(
+            parse(r#""a"[0,"#),
+            Err(KjqlParserError::ParsingError {
+                tokens: [Token::KeySelector("a")].stringify(),
+                unparsed: "[0,".to_string(),
+                offset: 6,
+                expected: vec!["a number".to_string(), "`:`".to_string(), "`-`".to_string()],
+            })
+        );
+        assert_eq!(
+            parse("[0 x]"),
+            Err(KjqlParserError::ParsingError {
+                tokens: String::new(),
+                unparsed: "[0 x]".to_string(),
+                offset: 3,
+                expected: vec!["`]`".to_string(), "`,`".to_string(), "`:`".to_string()],
+            })
+        );
+        assert_eq!(
+            parse(r#""a" "b"#),
+            Err(KjqlParserError::ParsingError {
+                tokens: [Token::KeySelector("a")].stringify(),
+                unparsed: r#""b"#.to_string(),
+                offset: 6,
+                expected: vec![r#"`"`"#.to_string()],
+            })
+        );
+        assert_eq!(
+            parse(r#"if "a" then "b" end"#),
+            Err(KjqlParserError::ParsingError {
+                tokens: String::new(),
+                unparsed: r#"if "a" then "b" end"#.to_string(),
+                offset: 16,
+                expected: vec!["`else`".to_string()],
+            })
+        );
+        assert_eq!(
+            parse(r#""a"|={"b"=1 "c"}"#),
+            Err(KjqlParserError::ParsingError {
+                tokens: [Token::KeySelector("a")].stringify(),
+                unparsed: r#"|={"b"=1 "c"}"#.to_string(),
+                offset: 12,
+                expected: vec!["`}`".to_string(), "`,`".to_string()],
+            })
+        );
+        assert_eq!(
+            parse("  "),
+            Err(KjqlParserError::ParsingError {
+                tokens: String::new(),
+                unparsed: "  ".to_string(),
+                offset: 2,
+                expected: vec!["a selector or an operator".to_string()],
+            })
+        );
+    }
+
     #[test]
     fn check_full_parser() {
         assert_eq!(
@@ -311,6 +905,8 @@ mod tests {
             Err(KjqlParserError::ParsingError {
                 tokens: [Token::ArrayIndexSelector(vec![Index(9), Index(0)])].stringify(),
                 unparsed: "nope".to_string(),
+                offset: 5,
+                expected: vec!["a selector or an operator".to_string()],
             })
         );
         assert_eq!(
@@ -338,4 +934,43 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn check_comments() {
+        assert_eq!(
+            parse(
+                r#"# Containers images.
+                "spec" /* "status" */ "containers" # every container
+                |> "image" /* multi
+                line */ <|"#
+            ),
+            Ok(vec![
+                Token::KeySelector("spec"),
+                Token::KeySelector("containers"),
+                Token::PipeInOperator,
+                Token::KeySelector("image"),
+                Token::PipeOutOperator,
+            ])
+        );
+        assert_eq!(
+            parse(r#""a # b" |={"c"="/* d */"}"#),
+            Ok(vec![
+                Token::KeySelector("a # b"),
+                Token::LensSelector(vec![Lens(
+                    vec![Token::KeySelector("c")],
+                    Some(LensValue::String("/* d */"))
+                )]),
+            ])
+        );
+        assert_eq!(
+            parse(r#""a" / "b""#),
+            Ok(vec![Token::Expression(Expression::binary(
+                Expression::Operand(vec![Token::KeySelector("a")]),
+                BinaryOperator::Divide,
+                Expression::Operand(vec![Token::KeySelector("b")]),
+            ))])
+        );
+        assert_eq!(parse("\"a\" # trailing"), Ok(vec![Token::KeySelector("a")]));
+        assert!(parse(r#""a" /* unterminated"#).is_err());
+    }
 }
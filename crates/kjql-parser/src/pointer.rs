@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+
+use crate::{
+    errors::KjqlParserError,
+    tokens::{
+        Index,
+        Token,
+    },
+};
+
+/// Separator of the reference tokens.
+static SEPARATOR: char = '/';
+/// Escape character.
+static TILDE: char = '~';
+
+/// Unescapes a reference token, `~1` being `/` and `~0` being `~`.
+/// Returns `None` if the reference token contains an invalid escape sequence.
+fn unescape(reference: &str) -> Option<Cow<'_, str>> {
+    if !reference.contains(TILDE) {
+        return Some(Cow::Borrowed(reference));
+    }
+
+    let mut result = String::with_capacity(reference.len());
+    let mut chars = reference.chars();
+
+    while let Some(char) = chars.next() {
+        if char == TILDE {
+            match chars.next() {
+                Some('0') => result.push(TILDE),
+                Some('1') => result.push(SEPARATOR),
+                _ => return None,
+            }
+        } else {
+            result.push(char);
+        }
+    }
+
+    Some(Cow::Owned(result))
+}
+
+/// Parses a JSON Pointer (RFC 6901) like `/spec/containers/0/image` and
+/// returns its unescaped reference tokens.
+///
+/// # Errors
+///
+/// Returns a `KjqlParserError` if the input is not a valid JSON Pointer.
+pub fn parse_pointer(input: &str) -> Result<Vec<Cow<'_, str>>, KjqlParserError> {
+    let pointer_error = |reason: &str| KjqlParserError::PointerError {
+        pointer: input.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if input.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let Some(references) = input.strip_prefix(SEPARATOR) else {
+        return Err(pointer_error("a pointer must start with /"));
+    };
+
+    references
+        .split(SEPARATOR)
+        .map(|reference| {
+            unescape(reference).ok_or_else(|| pointer_error("~ must be followed by 0 or 1"))
+        })
+        .collect()
+}
+
+/// Returns the array index of a reference token, i.e. `0` or digits without a
+/// leading zero, if any.
+#[must_use]
+pub fn reference_to_index(reference: &str) -> Option<Index> {
+    let is_index = reference == "0"
+        || (!reference.starts_with('0')
+            && !reference.is_empty()
+            && reference.chars().all(|char| char.is_ascii_digit()));
+
+    reference.parse().ok().filter(|_| is_index)
+}
+
+/// Maps the reference tokens of a JSON Pointer to a vector of `Tokens`.
+/// Array indexes are mapped to `ReferenceSelector` since they select a key
+/// on objects and an index on arrays, any other reference token to
+/// `KeySelector`.
+#[must_use]
+pub fn pointer_to_tokens<'a>(references: &'a [Cow<'_, str>]) -> Vec<Token<'a>> {
+    references
+        .iter()
+        .map(|reference| match reference_to_index(reference) {
+            Some(_) => Token::ReferenceSelector(reference),
+            None => Token::KeySelector(reference),
+        })
+        .collect()
+}
+
+/// Escapes a key as a reference token, `~` being `~0` and `/` being `~1`.
+#[must_use]
+pub fn escape_reference(key: &str) -> String {
+    key.replace(TILDE, "~0").replace(SEPARATOR, "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{
+        escape_reference,
+        parse_pointer,
+        pointer_to_tokens,
+        reference_to_index,
+    };
+    use crate::{
+        errors::KjqlParserError,
+        tokens::{
+            Index,
+            Token,
+        },
+    };
+
+    #[test]
+    fn check_parse_pointer() {
+        assert_eq!(parse_pointer(""), Ok(vec![]));
+        assert_eq!(parse_pointer("/"), Ok(vec![Cow::Borrowed("")]));
+        assert_eq!(
+            parse_pointer("/spec/containers/0/image"),
+            Ok(vec![
+                Cow::Borrowed("spec"),
+                Cow::Borrowed("containers"),
+                Cow::Borrowed("0"),
+                Cow::Borrowed("image"),
+            ])
+        );
+        assert_eq!(
+            parse_pointer("/a~1b/m~0n/~01"),
+            Ok(vec![
+                Cow::<str>::Owned("a/b".to_string()),
+                Cow::Owned("m~n".to_string()),
+                Cow::Owned("~1".to_string()),
+            ])
+        );
+        assert_eq!(
+            parse_pointer("a/b"),
+            Err(KjqlParserError::PointerError {
+                pointer: "a/b".to_string(),
+                reason: "a pointer must start with /".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_pointer("/a~2"),
+            Err(KjqlParserError::PointerError {
+                pointer: "/a~2".to_string(),
+                reason: "~ must be followed by 0 or 1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn check_pointer_to_tokens() {
+        let references = parse_pointer("/spec/containers/0/01/a~1b/10").unwrap();
+
+        assert_eq!(
+            pointer_to_tokens(&references),
+            vec![
+                Token::KeySelector("spec"),
+                Token::KeySelector("containers"),
+                Token::ReferenceSelector("0"),
+                Token::KeySelector("01"),
+                Token::KeySelector("a/b"),
+                Token::ReferenceSelector("10"),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reference_to_index() {
+        assert_eq!(reference_to_index("0"), Some(Index(0)));
+        assert_eq!(reference_to_index("404"), Some(Index(404)));
+        assert_eq!(reference_to_index("01"), None);
+        assert_eq!(reference_to_index("-"), None);
+        assert_eq!(reference_to_index(""), None);
+        assert_eq!(reference_to_index("1a"), None);
+    }
+
+    #[test]
+    fn check_escape_reference() {
+        assert_eq!(escape_reference("a/b~c"), "a~1b~0c");
+        assert_eq!(escape_reference("abc"), "abc");
+    }
+}
@@ -0,0 +1,905 @@
+use crate::{
+    errors::KjqlParserError,
+    tokens::{
+        Alignment,
+        Expression,
+        Index,
+        Lens,
+        LensValue,
+        MergeStrategy,
+        Number,
+        Order,
+        Range,
+        Selection,
+        Token,
+    },
+};
+
+/// Indentation used by the formatter.
+static INDENT: &str = "  ";
+
+/// Returns a `KjqlParserError` for a token which can't be serialized.
+fn serialize_error(token: &Token, reason: &str) -> KjqlParserError {
+    KjqlParserError::SerializeError {
+        token: token.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Returns `true` if the token is a selector, the only tokens allowed in lenses
+/// and sub-queries.
+fn is_selector(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::ArrayIndexSelector(_)
+            | Token::ArrayMixedSelector(_)
+            | Token::ArrayRangeSelector(_)
+            | Token::KeySelector(_)
+            | Token::MultiKeySelector(_)
+            | Token::ObjectIndexSelector(_)
+            | Token::ObjectKeyRangeSelector(_)
+            | Token::ObjectMixedSelector(_)
+            | Token::ObjectRangeSelector(_)
+    )
+}
+
+/// Returns `true` if the token bounds the operands of an expression.
+fn is_boundary(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::GroupSeparator | Token::PipeInOperator | Token::PipeOutOperator
+    )
+}
+
+/// Serializes a key surrounded by double quotes.
+fn serialize_key(token: &Token, key: &str) -> Result<String, KjqlParserError> {
+    if key.contains('"') {
+        return Err(serialize_error(token, "keys can't contain double quotes"));
+    }
+
+    Ok(format!(r#""{key}""#))
+}
+
+/// Serializes a list of keys separated by commas.
+fn serialize_keys(token: &Token, keys: &[&str]) -> Result<String, KjqlParserError> {
+    if keys.is_empty() {
+        return Err(serialize_error(token, "lists can't be empty"));
+    }
+
+    Ok(keys
+        .iter()
+        .map(|key| serialize_key(token, key))
+        .collect::<Result<Vec<String>, KjqlParserError>>()?
+        .join(","))
+}
+
+/// Serializes a list of indexes separated by commas.
+fn serialize_indexes(token: &Token, indexes: &[Index]) -> Result<String, KjqlParserError> {
+    if indexes.is_empty() {
+        return Err(serialize_error(token, "lists can't be empty"));
+    }
+
+    Ok(indexes
+        .iter()
+        .map(|index| index.0.to_string())
+        .collect::<Vec<String>>()
+        .join(","))
+}
+
+/// Serializes a range without delimiters.
+fn serialize_range(range: &Range) -> String {
+    let serialize_bound =
+        |bound: Option<Index>| bound.map_or(String::new(), |index| index.0.to_string());
+
+    format!("{}:{}", serialize_bound(range.0), serialize_bound(range.1))
+}
+
+/// Serializes a list of indexes and ranges separated by commas.
+fn serialize_selections(
+    token: &Token,
+    selections: &[Selection],
+) -> Result<String, KjqlParserError> {
+    if selections.is_empty() {
+        return Err(serialize_error(token, "lists can't be empty"));
+    }
+
+    Ok(selections
+        .iter()
+        .map(|selection| match selection {
+            Selection::Index(index) => index.0.to_string(),
+            Selection::IndexFromEnd(index) => format!("-{}", index.0),
+            Selection::Range(range) => serialize_range(range),
+        })
+        .collect::<Vec<String>>()
+        .join(","))
+}
+
+/// Serializes a number literal.
+/// Floats always have a fractional part so that they are not parsed back as
+/// integers.
+fn serialize_number(token: &Token, number: &Number) -> Result<String, KjqlParserError> {
+    match number {
+        Number::Integer(integer) => Ok(integer.to_string()),
+        Number::Float(float) if float.is_finite() && float.is_sign_positive() => {
+            let float = float.to_string();
+
+            if float.contains('.') {
+                Ok(float)
+            } else {
+                Ok(format!("{float}.0"))
+            }
+        }
+        Number::Float(_) => Err(serialize_error(
+            token,
+            "only finite and positive numbers are supported",
+        )),
+    }
+}
+
+/// Serializes a path made of selectors, as used by lenses and sub-queries.
+fn serialize_path(token: &Token, tokens: &[Token]) -> Result<String, KjqlParserError> {
+    if tokens.is_empty() {
+        return Err(serialize_error(token, "paths can't be empty"));
+    }
+
+    if !tokens.iter().all(is_selector) {
+        return Err(serialize_error(
+            token,
+            "only selectors are supported in lenses and sub-queries",
+        ));
+    }
+
+    Ok(tokens
+        .iter()
+        .map(serialize_token)
+        .collect::<Result<Vec<String>, KjqlParserError>>()?
+        .concat())
+}
+
+/// Serializes a list of sub-queries surrounded by parentheses.
+fn serialize_sub_queries(
+    token: &Token,
+    sub_queries: &[Vec<Token>],
+) -> Result<String, KjqlParserError> {
+    if sub_queries.len() < 2 {
+        return Err(serialize_error(
+            token,
+            "at least two sub-queries are required",
+        ));
+    }
+
+    Ok(format!(
+        "({})",
+        sub_queries
+            .iter()
+            .map(|sub_query| serialize_path(token, sub_query))
+            .collect::<Result<Vec<String>, KjqlParserError>>()?
+            .join(",")
+    ))
+}
+
+/// Serializes a lens value.
+fn serialize_lens_value(token: &Token, value: &LensValue) -> Result<String, KjqlParserError> {
+    match value {
+        LensValue::Bool(boolean) => Ok(boolean.to_string()),
+        LensValue::Exists => Ok("exists".to_string()),
+        LensValue::Null => Ok("null".to_string()),
+        LensValue::Number(number) => Ok(number.to_string()),
+        LensValue::String(string) => serialize_key(token, string),
+        LensValue::Type(json_type) => Ok(format!("<{json_type}>")),
+    }
+}
+
+/// Serializes a list of lenses separated by commas.
+fn serialize_lenses(token: &Token, lenses: &[Lens]) -> Result<String, KjqlParserError> {
+    if lenses.is_empty() {
+        return Err(serialize_error(token, "lists can't be empty"));
+    }
+
+    Ok(lenses
+        .iter()
+        .map(|Lens(tokens, value)| {
+            let path = serialize_path(token, tokens)?;
+
+            match value {
+                Some(value) => Ok(format!("{path}={}", serialize_lens_value(token, value)?)),
+                None => Ok(path),
+            }
+        })
+        .collect::<Result<Vec<String>, KjqlParserError>>()?
+        .join(","))
+}
+
+/// Serializes an expression.
+/// There is no grouping in the grammar, the operands must follow the
+/// precedence and the left associativity of the operators.
+fn serialize_expression(token: &Token, expression: &Expression) -> Result<String, KjqlParserError> {
+    match expression {
+        Expression::Binary(left, operator, right) => {
+            let needs_grouping = |operand: &Expression, is_right: bool| match operand {
+                Expression::Binary(_, inner, _) => {
+                    inner.precedence() < operator.precedence()
+                        || (is_right && inner.precedence() == operator.precedence())
+                }
+                Expression::Operand(_) => false,
+            };
+
+            if needs_grouping(left, false) || needs_grouping(right, true) {
+                return Err(serialize_error(
+                    token,
+                    "the expression can't be written without parentheses",
+                ));
+            }
+
+            Ok(format!(
+                "{} {operator} {}",
+                serialize_expression(token, left)?,
+                serialize_expression(token, right)?
+            ))
+        }
+        Expression::Operand(tokens) => {
+            if tokens.is_empty() {
+                return Err(serialize_error(token, "operands can't be empty"));
+            }
+
+            if tokens
+                .iter()
+                .any(|token| is_boundary(token) || matches!(token, Token::Expression(_)))
+            {
+                return Err(serialize_error(
+                    token,
+                    "operands can't contain expressions, group separators or pipes",
+                ));
+            }
+
+            serialize(tokens)
+        }
+    }
+}
+
+/// Returns the suffix of the operators with an `Alignment`.
+fn alignment_suffix(alignment: Alignment) -> &'static str {
+    match alignment {
+        Alignment::Pad => "_pad",
+        Alignment::Strict => "",
+    }
+}
+
+/// Returns the suffix of the operators with an `Order`.
+fn order_suffix(order: Order) -> &'static str {
+    match order {
+        Order::Ascending => "",
+        Order::Descending => "_desc",
+    }
+}
+
+/// Serializes a single token.
+fn serialize_token(token: &Token) -> Result<String, KjqlParserError> {
+    match token {
+        Token::ArrayIndexSelector(indexes) => {
+            Ok(format!("[{}]", serialize_indexes(token, indexes)?))
+        }
+        Token::ArrayMixedSelector(selections) => {
+            Ok(format!("[{}]", serialize_selections(token, selections)?))
+        }
+        Token::ArrayRangeSelector(range) => Ok(format!("[{}]", serialize_range(range))),
+        Token::AverageOperator => Ok("avg".to_string()),
+        Token::Conditional(lenses, consequent, alternative) => {
+            if consequent.is_empty() || alternative.is_empty() {
+                return Err(serialize_error(token, "branches can't be empty"));
+            }
+
+            Ok(format!(
+                "if {} then {} else {} end",
+                serialize_lenses(token, lenses)?,
+                serialize(consequent)?,
+                serialize(alternative)?
+            ))
+        }
+        Token::DescendantsOperator => Ok("descendants".to_string()),
+        Token::Expression(expression) => serialize_expression(token, expression),
+        Token::FirstOperator => Ok("first".to_string()),
+        Token::FlattenOperator => Ok("..".to_string()),
+        Token::FromJsonOperator => Ok("fromjson".to_string()),
+        Token::GroupByOperator(tokens) => {
+            Ok(format!("group_by({})", serialize_path(token, tokens)?))
+        }
+        Token::GroupSeparator => Ok(",".to_string()),
+        Token::HasIndexOperator(index) => Ok(format!("has({})", index.0)),
+        Token::HasKeyOperator(key) => Ok(format!("has({})", serialize_key(token, key)?)),
+        Token::JoinOperator(separator) => Ok(format!("join({})", serialize_key(token, separator)?)),
+        Token::KeyOperator => Ok("@".to_string()),
+        Token::KeySelector(key) => serialize_key(token, key),
+        Token::JsonPathSelector(_) => Err(serialize_error(
+            token,
+            "JSONPath segments have no query syntax",
+        )),
+        Token::ReferenceSelector(_) => Err(serialize_error(
+            token,
+            "references are resolved against the JSON value and have no query syntax",
+        )),
+        Token::LastOperator => Ok("last".to_string()),
+        Token::LensSelector(lenses) => Ok(format!("|={{{}}}", serialize_lenses(token, lenses)?)),
+        Token::LimitOperator(count) => Ok(format!("limit({count})")),
+        Token::LowerOperator => Ok("lower".to_string()),
+        Token::MaxByOperator(tokens) => Ok(format!("max_by({})", serialize_path(token, tokens)?)),
+        Token::MaxOperator => Ok("max".to_string()),
+        Token::MergeOperator(sub_queries, strategy) => {
+            let name = match strategy {
+                MergeStrategy::Concatenate => "merge_concat",
+                MergeStrategy::Replace => "merge",
+            };
+
+            Ok(format!(
+                "{name}{}",
+                serialize_sub_queries(token, sub_queries)?
+            ))
+        }
+        Token::MinByOperator(tokens) => Ok(format!("min_by({})", serialize_path(token, tokens)?)),
+        Token::MinOperator => Ok("min".to_string()),
+        Token::MultiKeySelector(keys) => Ok(format!("{{{}}}", serialize_keys(token, keys)?)),
+        Token::NumberLiteral(number) => serialize_number(token, number),
+        Token::ObjectIndexSelector(indexes) => {
+            Ok(format!("{{{}}}", serialize_indexes(token, indexes)?))
+        }
+        Token::ObjectKeyRangeSelector(range) => {
+            let (start, end, inclusive) = range.get();
+
+            Ok(format!(
+                "{{{}{}{}}}",
+                serialize_key(token, start)?,
+                if inclusive { ":" } else { ".." },
+                serialize_key(token, end)?
+            ))
+        }
+        Token::ObjectMixedSelector(selections) => {
+            Ok(format!("{{{}}}", serialize_selections(token, selections)?))
+        }
+        Token::ObjectRangeSelector(range) => Ok(format!("{{{}}}", serialize_range(range))),
+        Token::PipeInOperator => Ok("|>".to_string()),
+        Token::PipeOutOperator => Ok("<|".to_string()),
+        Token::ReplaceOperator(from, to) => Ok(format!(
+            "replace({},{})",
+            serialize_key(token, from)?,
+            serialize_key(token, to)?
+        )),
+        Token::SkipOperator(count) => Ok(format!("skip({count})")),
+        Token::SortByOperator(tokens, order) => Ok(format!(
+            "sort_by{}({})",
+            order_suffix(*order),
+            serialize_path(token, tokens)?
+        )),
+        Token::SortOperator(order) => Ok(format!("sort{}", order_suffix(*order))),
+        Token::SplitOperator(separator) => {
+            Ok(format!("split({})", serialize_key(token, separator)?))
+        }
+        Token::SumOperator => Ok("sum".to_string()),
+        Token::ToJsonOperator => Ok("tojson".to_string()),
+        Token::ToNumberOperator => Ok("tonumber".to_string()),
+        Token::ToStringOperator => Ok("tostring".to_string()),
+        Token::TransposeOperator(alignment) => {
+            Ok(format!("transpose{}", alignment_suffix(*alignment)))
+        }
+        Token::TrimOperator => Ok("trim".to_string()),
+        Token::TruncateOperator => Ok("!".to_string()),
+        Token::TypeOperator => Ok("type".to_string()),
+        Token::TypeSelector(json_type) => Ok(format!("<{json_type}>")),
+        Token::UniqueByOperator(tokens) => {
+            Ok(format!("unique_by({})", serialize_path(token, tokens)?))
+        }
+        Token::UniqueOperator => Ok("unique".to_string()),
+        Token::UpperOperator => Ok("upper".to_string()),
+        Token::ValuesOperator => Ok("values".to_string()),
+        Token::ZipObjectOperator(sub_queries, alignment) => Ok(format!(
+            "zip_object{}{}",
+            alignment_suffix(*alignment),
+            serialize_sub_queries(token, sub_queries)?
+        )),
+        Token::ZipOperator(sub_queries, alignment) => Ok(format!(
+            "zip{}{}",
+            alignment_suffix(*alignment),
+            serialize_sub_queries(token, sub_queries)?
+        )),
+    }
+}
+
+/// Returns `true` if the serialized tokens must be separated by a whitespace
+/// so that they are not parsed as a single token, i.e. `sum avg`.
+fn needs_whitespace(previous: &str, next: &str) -> bool {
+    let is_word = |char: char| char.is_alphanumeric() || char == '_';
+
+    previous.ends_with(is_word) && next.starts_with(is_word)
+}
+
+/// Checks that the expressions are bounded by group separators or pipes, as
+/// operands would be merged with the surrounding tokens otherwise.
+fn check_expressions(tokens: &[Token]) -> Result<(), KjqlParserError> {
+    let is_bounded = |neighbor: Option<&Token>| neighbor.is_none_or(is_boundary);
+
+    for (index, token) in tokens.iter().enumerate() {
+        if let Token::Expression(_) = token {
+            if !is_bounded(index.checked_sub(1).and_then(|index| tokens.get(index)))
+                || !is_bounded(tokens.get(index + 1))
+            {
+                return Err(serialize_error(
+                    token,
+                    "an expression must be bounded by group separators or pipes",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Serializes a slice of `Tokens` to a canonical kjql query.
+/// Parsing the query gives back the same tokens.
+///
+/// # Errors
+///
+/// Returns a `KjqlParserError` if a token can't be written with the kjql
+/// syntax, i.e. a key with a double quote or an expression which is not
+/// bounded by group separators or pipes.
+pub fn serialize(tokens: &[Token]) -> Result<String, KjqlParserError> {
+    check_expressions(tokens)?;
+
+    let mut query = String::new();
+
+    for token in tokens {
+        let serialized = serialize_token(token)?;
+
+        if needs_whitespace(&query, &serialized) {
+            query.push(' ');
+        }
+
+        query.push_str(&serialized);
+    }
+
+    Ok(query)
+}
+
+/// Formats a slice of `Tokens` at the given indentation depth.
+fn format_tokens(tokens: &[Token], depth: usize) -> Result<String, KjqlParserError> {
+    check_expressions(tokens)?;
+
+    let mut query = String::new();
+    let mut previous: Option<&Token> = None;
+
+    for token in tokens {
+        let formatted = match token {
+            Token::Conditional(lenses, consequent, alternative) => {
+                if consequent.is_empty() || alternative.is_empty() {
+                    return Err(serialize_error(token, "branches can't be empty"));
+                }
+
+                format!(
+                    "if {} then {} else {} end",
+                    serialize_lenses(token, lenses)?,
+                    format_tokens(consequent, depth)?,
+                    format_tokens(alternative, depth)?
+                )
+            }
+            Token::LensSelector(lenses) if lenses.len() > 1 => {
+                let indentation = INDENT.repeat(depth + 1);
+                let lenses = lenses
+                    .iter()
+                    .map(|lens| {
+                        serialize_lenses(token, std::slice::from_ref(lens))
+                            .map(|lens| format!("{indentation}{lens}"))
+                    })
+                    .collect::<Result<Vec<String>, KjqlParserError>>()?
+                    .join(",\n");
+
+                format!("|={{\n{lenses}\n{}}}", INDENT.repeat(depth))
+            }
+            _ => serialize_token(token)?,
+        };
+
+        match previous {
+            Some(Token::GroupSeparator) => {
+                query.push('\n');
+                query.push_str(&INDENT.repeat(depth));
+            }
+            Some(previous)
+                if *token != Token::GroupSeparator
+                    && !(is_selector(previous) && is_selector(token)) =>
+            {
+                query.push(' ');
+            }
+            _ => {}
+        }
+
+        query.push_str(&formatted);
+        previous = Some(token);
+    }
+
+    Ok(query)
+}
+
+/// Formats a slice of `Tokens` to a readable kjql query.
+/// Selectors are chained, the other tokens are separated by a whitespace, each
+/// group is written on its own line and lens selectors with multiple lenses
+/// are indented with one lens per line.
+///
+/// # Errors
+///
+/// Returns a `KjqlParserError` if a token can't be written with the kjql
+/// syntax.
+pub fn format(tokens: &[Token]) -> Result<String, KjqlParserError> {
+    format_tokens(tokens, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{
+        format,
+        serialize,
+    };
+    use crate::{
+        errors::KjqlParserError,
+        parser::parse,
+        tokens::{
+            BinaryOperator,
+            Expression,
+            Index,
+            Lens,
+            MergeStrategy,
+            Number,
+            Token,
+        },
+    };
+
+    /// Parses the query, serializes the tokens and parses the result again.
+    fn round_trip(query: &str) -> String {
+        let tokens = parse(query).unwrap();
+        let serialized = serialize(&tokens).unwrap();
+
+        assert_eq!(parse(&serialized), Ok(tokens), "{serialized}");
+
+        serialized
+    }
+
+    #[test]
+    fn check_serialize() {
+        assert_eq!(
+            round_trip(
+                r#" "a" [0, 1] [1:] [0,1:2] {"b","c"} {0, 2} {:1} {"b".."c"} {0,1:} [0, -1] {- 2}"#
+            ),
+            r#""a"[0,1][1:][0,1:2]{"b","c"}{0,2}{:1}{"b".."c"}{0,1:}[0,-1]{-2}"#
+        );
+        assert_eq!(
+            round_trip(r#""a" |> "b" <| sum avg , "c" .. !"#),
+            r#""a"|>"b"<|sum avg,"c"..!"#
+        );
+        assert_eq!(
+            round_trip(r#""a" |={"b"="c", "d"[0]=1, "e"=<array>, "f", "g"=exists, "h"=null}"#),
+            r#""a"|={"b"="c","d"[0]=1,"e"=<array>,"f","g"=exists,"h"=null}"#
+        );
+        assert_eq!(
+            round_trip(r#"if "a"=true then "b" else "c" sum end"#),
+            r#"if "a"=true then "b" else "c"sum end"#
+        );
+        assert_eq!(
+            round_trip(r#""a" + "b" * 2 - 1.5 | "c""#),
+            r#""a" + "b" * 2 - 1.5 | "c""#
+        );
+        assert_eq!(
+            round_trip(
+                r#"sort_by_desc("a") unique_by("b") group_by("c") min_by("d") max_by("e") sort_desc"#
+            ),
+            r#"sort_by_desc("a")unique_by("b")group_by("c")min_by("d")max_by("e")sort_desc"#
+        );
+        assert_eq!(
+            round_trip(
+                r#"merge_concat("a","b") zip_pad("a","b") zip_object("a","b") transpose_pad"#
+            ),
+            r#"merge_concat("a","b")zip_pad("a","b")zip_object("a","b")transpose_pad"#
+        );
+        assert_eq!(
+            round_trip(r#"has("a") has(0) join(",") split("") replace("a","b") limit(2) skip(1)"#),
+            r#"has("a")has(0)join(",")split("")replace("a","b")limit(2)skip(1)"#
+        );
+        assert_eq!(
+            round_trip("first last lower upper trim tostring tonumber tojson fromjson type @"),
+            "first last lower upper trim tostring tonumber tojson fromjson type@"
+        );
+        assert_eq!(
+            round_trip("<number> values descendants unique min max"),
+            "<number>values descendants unique min max"
+        );
+        assert_eq!(
+            serialize(&[Token::NumberLiteral(Number::Float(2.0))]),
+            Ok("2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn check_format() {
+        let format_query = |query| format(&parse(query).unwrap()).unwrap();
+
+        assert_eq!(
+            format_query(r#""a""b"[0]|>"c"<|sum,"d"..,"e"+"f"*2,"g"!"#),
+            "\"a\"\"b\"[0] |> \"c\" <| sum,\n\"d\" ..,\n\"e\" + \"f\" * 2,\n\"g\" !"
+        );
+        assert_eq!(
+            format_query(r#""a"|={"b"="c","d"}|={"e"}"#),
+            "\"a\" |={\n  \"b\"=\"c\",\n  \"d\"\n} |={\"e\"}"
+        );
+        assert_eq!(
+            format_query(r#"if "a" then "b"|={"c","d"},"e" else "f" end"#),
+            "if \"a\" then \"b\" |={\n  \"c\",\n  \"d\"\n},\n\"e\" else \"f\" end"
+        );
+    }
+
+    #[test]
+    fn check_serialize_errors() {
+        assert_eq!(
+            serialize(&[Token::KeySelector(r#"a"b"#)]),
+            Err(KjqlParserError::SerializeError {
+                token: Token::KeySelector(r#"a"b"#).to_string(),
+                reason: "keys can't contain double quotes".to_string(),
+            })
+        );
+
+        let expression = Token::Expression(Expression::binary(
+            Expression::Operand(vec![Token::KeySelector("a")]),
+            BinaryOperator::Multiply,
+            Expression::binary(
+                Expression::Operand(vec![Token::KeySelector("b")]),
+                BinaryOperator::Add,
+                Expression::Operand(vec![Token::KeySelector("c")]),
+            ),
+        ));
+        assert_eq!(
+            serialize(std::slice::from_ref(&expression)),
+            Err(KjqlParserError::SerializeError {
+                token: expression.to_string(),
+                reason: "the expression can't be written without parentheses".to_string(),
+            })
+        );
+
+        let expression = Token::Expression(Expression::binary(
+            Expression::Operand(vec![Token::KeySelector("a")]),
+            BinaryOperator::Add,
+            Expression::Operand(vec![Token::KeySelector("b")]),
+        ));
+        assert_eq!(
+            serialize(&[Token::KeySelector("c"), expression.clone()]),
+            Err(KjqlParserError::SerializeError {
+                token: expression.to_string(),
+                reason: "an expression must be bounded by group separators or pipes".to_string(),
+            })
+        );
+
+        let lenses = Token::LensSelector(vec![Lens::new(&[Token::SumOperator], None)]);
+        assert_eq!(
+            serialize(std::slice::from_ref(&lenses)),
+            Err(KjqlParserError::SerializeError {
+                token: lenses.to_string(),
+                reason: "only selectors are supported in lenses and sub-queries".to_string(),
+            })
+        );
+        assert!(serialize(&[Token::ArrayIndexSelector(vec![])]).is_err());
+        assert!(
+            serialize(&[Token::MergeOperator(
+                vec![vec![Token::KeySelector("a")]],
+                MergeStrategy::Replace
+            )])
+            .is_err()
+        );
+        assert_eq!(
+            serialize(&[Token::ArrayIndexSelector(vec![Index(0)])]),
+            Ok("[0]".to_string())
+        );
+    }
+
+    /// Strategy generating keys.
+    fn key() -> impl Strategy<Value = String> {
+        "[a-z_ .,:$-]{0,4}".prop_map(|key| format!(r#""{key}""#))
+    }
+
+    /// Strategy generating optional numbers.
+    fn bound() -> impl Strategy<Value = String> {
+        prop_oneof![
+            Just(String::new()),
+            (0..20usize).prop_map(|index| index.to_string())
+        ]
+    }
+
+    /// Strategy generating indexes.
+    fn index() -> impl Strategy<Value = String> {
+        (0..20usize).prop_map(|index| index.to_string())
+    }
+
+    /// Strategy generating ranges without delimiters.
+    fn range() -> impl Strategy<Value = String> {
+        (bound(), bound()).prop_map(|(start, end)| format!("{start}:{end}"))
+    }
+
+    /// Strategy generating lists of indexes and ranges.
+    fn selections() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop_oneof![index(), range()], 2..4)
+            .prop_map(|selections| selections.join(" , "))
+    }
+
+    /// Strategy generating selectors.
+    fn selector() -> impl Strategy<Value = String> {
+        prop_oneof![
+            key(),
+            prop::collection::vec(index(), 1..4)
+                .prop_map(|indexes| format!("[{}]", indexes.join(","))),
+            range().prop_map(|range| format!("[{range}]")),
+            selections().prop_map(|selections| format!("[{selections}]")),
+            prop::collection::vec(key(), 1..4).prop_map(|keys| format!("{{{}}}", keys.join(","))),
+            prop::collection::vec(index(), 1..4)
+                .prop_map(|indexes| format!("{{{}}}", indexes.join(","))),
+            range().prop_map(|range| format!("{{{range}}}")),
+            (key(), prop_oneof![Just(":"), Just("..")], key())
+                .prop_map(|(start, separator, end)| format!("{{{start}{separator}{end}}}")),
+            selections().prop_map(|selections| format!("{{{selections}}}")),
+        ]
+    }
+
+    /// Strategy generating paths made of selectors.
+    fn path() -> impl Strategy<Value = String> {
+        prop::collection::vec(selector(), 1..3).prop_map(|selectors| selectors.concat())
+    }
+
+    /// Strategy generating lenses.
+    fn lenses() -> impl Strategy<Value = String> {
+        let value = prop_oneof![
+            Just(String::new()),
+            Just("=exists".to_string()),
+            Just("=true".to_string()),
+            Just("=null".to_string()),
+            (0..100usize).prop_map(|number| format!("={number}")),
+            key().prop_map(|key| format!("={key}")),
+            Just("=<string>".to_string()),
+        ];
+
+        prop::collection::vec((path(), value), 1..3).prop_map(|lenses| {
+            lenses
+                .into_iter()
+                .map(|(path, value)| format!("{path}{value}"))
+                .collect::<Vec<String>>()
+                .join(",")
+        })
+    }
+
+    /// Strategy generating fragments.
+    fn fragment() -> impl Strategy<Value = String> {
+        let word = prop::sample::select(vec![
+            "avg",
+            "descendants",
+            "..",
+            "first",
+            "fromjson",
+            "@",
+            "last",
+            "lower",
+            "max",
+            "min",
+            "sort",
+            "sort_desc",
+            "sum",
+            "tojson",
+            "tonumber",
+            "tostring",
+            "transpose",
+            "transpose_pad",
+            "trim",
+            "type",
+            "unique",
+            "upper",
+            "values",
+            "<object>",
+        ])
+        .prop_map(ToString::to_string);
+        let sub_query_operator = prop::sample::select(vec![
+            "group_by",
+            "max_by",
+            "min_by",
+            "sort_by",
+            "sort_by_desc",
+            "unique_by",
+        ]);
+        let sub_queries_operator = prop::sample::select(vec![
+            "merge",
+            "merge_concat",
+            "zip",
+            "zip_pad",
+            "zip_object",
+            "zip_object_pad",
+        ]);
+
+        prop_oneof![
+            4 => selector(),
+            2 => word,
+            1 => (sub_query_operator, path())
+                .prop_map(|(operator, path)| format!("{operator}({path})")),
+            1 => (sub_queries_operator, prop::collection::vec(path(), 2..4))
+                .prop_map(|(operator, paths)| format!("{operator}({})", paths.join(","))),
+            1 => key().prop_map(|key| format!("has({key})")),
+            1 => (0..10usize).prop_map(|index| format!("has({index})")),
+            1 => key().prop_map(|key| format!("join({key})")),
+            1 => key().prop_map(|key| format!("split({key})")),
+            1 => (key(), key()).prop_map(|(from, to)| format!("replace({from}, {to})")),
+            1 => (0..10usize).prop_map(|count| format!("limit({count})")),
+            1 => (0..10usize).prop_map(|count| format!("skip({count})")),
+            1 => lenses().prop_map(|lenses| format!("|={{{lenses}}}")),
+            1 => (lenses(), path(), path()).prop_map(|(lenses, consequent, alternative)| {
+                format!("if {lenses} then {consequent} else {alternative} end")
+            }),
+        ]
+    }
+
+    /// Strategy generating number literals.
+    fn literal() -> impl Strategy<Value = String> {
+        prop_oneof![
+            (0..1000u64).prop_map(|number| number.to_string()),
+            (0..1000u64, 0..100u64).prop_map(|(integer, decimals)| format!("{integer}.{decimals}")),
+        ]
+    }
+
+    /// Strategy generating terms made of fragments, number literals being
+    /// only generated as operands of binary expressions.
+    fn term() -> impl Strategy<Value = String> {
+        let fragments =
+            || prop::collection::vec(fragment(), 1..4).prop_map(|fragments| fragments.join(" "));
+        let operator = prop::sample::select(vec![" + ", " - ", " * ", " / ", " % "]);
+
+        prop_oneof![
+            4 => fragments(),
+            1 => (fragments(), operator, literal())
+                .prop_map(|(fragments, operator, literal)| format!("{fragments}{operator}{literal}")),
+        ]
+    }
+
+    /// Strategy generating queries made of terms separated by binary
+    /// operators, pipes and group separators.
+    fn query() -> impl Strategy<Value = String> {
+        let separator = prop::sample::select(vec![
+            " + ", " - ", " * ", " / ", " % ", " & ", " | ", ",", " , ", "|>", " <| ",
+        ]);
+
+        (
+            term(),
+            prop::collection::vec((separator, term()), 0..4),
+            prop::bool::ANY,
+        )
+            .prop_map(|(first, rest, truncate)| {
+                let mut query = first;
+
+                for (separator, term) in rest {
+                    query.push_str(separator);
+                    query.push_str(&term);
+                }
+
+                if truncate {
+                    query.push('!');
+                }
+
+                query
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn check_serialize_round_trip(query in query()) {
+            let tokens = parse(&query);
+
+            prop_assert!(tokens.is_ok(), "{}", query);
+
+            let tokens = tokens.unwrap();
+            let serialized = serialize(&tokens).unwrap();
+
+            prop_assert_eq!(parse(&serialized), Ok(tokens.clone()), "{}", serialized);
+
+            let formatted = format(&tokens).unwrap();
+
+            prop_assert_eq!(parse(&formatted), Ok(tokens), "{}", formatted);
+        }
+    }
+}
@@ -21,6 +21,14 @@ impl Index {
     pub fn new(index: usize) -> Index {
         Index(index)
     }
+
+    #[must_use]
+    /// Resolves an `Index` counted from the end of `len` elements, `Index(1)`
+    /// being the last element.
+    /// Returns `None` if the index goes past the first element.
+    pub fn from_end(self, len: usize) -> Option<Index> {
+        len.checked_sub(self.0).filter(|_| self.0 > 0).map(Index)
+    }
 }
 
 impl From<Index> for usize {
@@ -63,6 +71,20 @@ impl Range {
         let end = self.1.unwrap_or(Index(len.get() - 1));
         (start.0, end.0)
     }
+
+    #[must_use]
+    /// Maps a `Range` to a tuple of boundaries clamped to `len`.
+    /// Returns `None` if the range doesn't overlap the `len` elements.
+    pub fn to_clamped_boundaries(&self, len: NonZeroUsize) -> Option<(usize, usize)> {
+        let (start, end) = self.to_boundaries(len);
+        let last = len.get() - 1;
+
+        if start.min(end) > last || (self.1.is_none() && start > last) {
+            return None;
+        }
+
+        Some((start.min(last), end.min(last)))
+    }
 }
 
 impl fmt::Display for Range {
@@ -81,6 +103,56 @@ impl fmt::Display for Range {
     }
 }
 
+/// `Selection` used for selectors mixing indexes and ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// Variant for a single index.
+    Index(Index),
+    /// Variant for a single index counted from the end, `-1` being the last
+    /// element.
+    IndexFromEnd(Index),
+    /// Variant for a range.
+    Range(Range),
+}
+
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Selection::Index(index) => write!(f, "{index}"),
+            Selection::IndexFromEnd(index) => write!(f, "Index (-{})", index.0),
+            Selection::Range(range) => write!(f, "{range}"),
+        }
+    }
+}
+
+/// `KeyRange` used for objects.
+/// Internally mapped to a tuple of keys and a flag telling whether the end key
+/// is included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyRange<'a>(pub(crate) &'a str, pub(crate) &'a str, pub(crate) bool);
+
+impl<'a> KeyRange<'a> {
+    #[must_use]
+    /// Creates a new `KeyRange`.
+    pub fn new(start: &'a str, end: &'a str, inclusive: bool) -> KeyRange<'a> {
+        KeyRange(start, end, inclusive)
+    }
+
+    #[must_use]
+    /// Gets the content of a `KeyRange`.
+    pub fn get(&self) -> (&'a str, &'a str, bool) {
+        (self.0, self.1, self.2)
+    }
+}
+
+impl fmt::Display for KeyRange<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let separator = if self.2 { ":" } else { ".." };
+
+        write!(f, r#"KeyRange ["{}"{separator}"{}"]"#, self.0, self.1)
+    }
+}
+
 /// `Lens` used for `LensSelector`.
 /// Internally mapped to a tuple of `Option` of `Index`
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -112,26 +184,303 @@ impl fmt::Display for Lens<'_> {
     }
 }
 
+/// Selector of a JSONPath segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSelector<'a> {
+    /// Filter expression, i.e. `[?@.a == 1 || @.b]`.
+    Filter(Vec<Lens<'a>>),
+    /// Array indexes, i.e. `[0]` or `[0,2]`.
+    Indexes(Vec<Index>),
+    /// Member names, i.e. `.a`, `['a']` or `['a','b']`.
+    Keys(Vec<&'a str>),
+    /// Array slice with an exclusive end, i.e. `[1:3]`.
+    Slice(Index, Option<Index>),
+    /// Wildcard, i.e. `.*` or `[*]`.
+    Wildcard,
+}
+
+impl PathSelector<'_> {
+    #[must_use]
+    /// Returns `true` if the selector selects at most one node.
+    pub fn is_singular(&self) -> bool {
+        match self {
+            PathSelector::Indexes(indexes) => indexes.len() == 1,
+            PathSelector::Keys(keys) => keys.len() == 1,
+            PathSelector::Filter(_) | PathSelector::Slice(..) | PathSelector::Wildcard => false,
+        }
+    }
+}
+
+impl fmt::Display for PathSelector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSelector::Filter(lenses) => {
+                let formatted_lenses = lenses
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "Filter [{formatted_lenses}]")
+            }
+            PathSelector::Indexes(indexes) => {
+                let formatted_indexes = indexes
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "Indexes [{formatted_indexes}]")
+            }
+            PathSelector::Keys(keys) => write!(f, "Keys {}", keys.join(",")),
+            PathSelector::Slice(start, end) => write!(
+                f,
+                "Slice [{}:{}]",
+                start.0,
+                end.map_or(String::new(), |end| end.0.to_string())
+            ),
+            PathSelector::Wildcard => write!(f, "Wildcard"),
+        }
+    }
+}
+
+/// `PathSegment` of a JSONPath.
+/// Internally mapped to a tuple of a flag telling whether the selector is
+/// applied to the descendants instead of the children and of the selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSegment<'a>(pub(crate) bool, pub(crate) PathSelector<'a>);
+
+impl<'a> PathSegment<'a> {
+    #[must_use]
+    /// Creates a new `PathSegment`.
+    pub fn new(descendant: bool, selector: PathSelector<'a>) -> PathSegment<'a> {
+        PathSegment(descendant, selector)
+    }
+
+    #[must_use]
+    /// Gets the content of a `PathSegment`.
+    pub fn get(&self) -> (bool, &PathSelector<'a>) {
+        (self.0, &self.1)
+    }
+}
+
+impl fmt::Display for PathSegment<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0 {
+            write!(f, "Descendants {}", self.1)
+        } else {
+            write!(f, "{}", self.1)
+        }
+    }
+}
+
 /// Lens value type.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LensValue<'a> {
     /// Variant for a JSON boolean
     Bool(bool),
+    /// Variant for an existing path.
+    Exists,
     /// Variant for JSON null.
     Null,
     /// Variant for a JSON number.
     Number(usize),
     /// Variant for a JSON string.
     String(&'a str),
+    /// Variant for a JSON type.
+    Type(JsonType),
 }
 
 impl fmt::Display for LensValue<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             LensValue::Bool(boolean) => write!(f, "{boolean}"),
+            LensValue::Exists => write!(f, "Exists"),
             LensValue::Null => write!(f, "Null"),
             LensValue::Number(number) => write!(f, "{number}"),
             LensValue::String(string) => write!(f, "{string}"),
+            LensValue::Type(json_type) => write!(f, "<{json_type}>"),
+        }
+    }
+}
+
+/// JSON type used by type predicates and type selectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    /// JSON array.
+    Array,
+    /// JSON boolean.
+    Boolean,
+    /// JSON null.
+    Null,
+    /// JSON number.
+    Number,
+    /// JSON object.
+    Object,
+    /// JSON string.
+    String,
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonType::Array => write!(f, "array"),
+            JsonType::Boolean => write!(f, "boolean"),
+            JsonType::Null => write!(f, "null"),
+            JsonType::Number => write!(f, "number"),
+            JsonType::Object => write!(f, "object"),
+            JsonType::String => write!(f, "string"),
+        }
+    }
+}
+
+/// Number literal.
+/// Integers are kept apart from floats to preserve their semantics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// Variant for an integer.
+    Integer(u64),
+    /// Variant for a float.
+    Float(f64),
+}
+
+// The parser only produces finite floats.
+impl Eq for Number {}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(integer) => write!(f, "{integer}"),
+            Number::Float(float) => write!(f, "{float:?}"),
+        }
+    }
+}
+
+/// Binary operator used in expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    /// Addition.
+    Add,
+    /// Division.
+    Divide,
+    /// Intersection.
+    Intersection,
+    /// Multiplication.
+    Multiply,
+    /// Remainder.
+    Remainder,
+    /// Subtraction or difference.
+    Subtract,
+    /// Union.
+    Union,
+}
+
+impl BinaryOperator {
+    #[must_use]
+    /// Returns the precedence of the operator, higher binds tighter.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Union => 1,
+            BinaryOperator::Intersection => 2,
+            BinaryOperator::Add | BinaryOperator::Subtract => 3,
+            BinaryOperator::Divide | BinaryOperator::Multiply | BinaryOperator::Remainder => 4,
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryOperator::Add => write!(f, "+"),
+            BinaryOperator::Divide => write!(f, "/"),
+            BinaryOperator::Intersection => write!(f, "&"),
+            BinaryOperator::Multiply => write!(f, "*"),
+            BinaryOperator::Remainder => write!(f, "%"),
+            BinaryOperator::Subtract => write!(f, "-"),
+            BinaryOperator::Union => write!(f, "|"),
+        }
+    }
+}
+
+/// Expression tree used for binary operations between sub-queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression<'a> {
+    /// Binary operation between two expressions.
+    Binary(Box<Expression<'a>>, BinaryOperator, Box<Expression<'a>>),
+    /// Operand as a sub-query.
+    Operand(Vec<Token<'a>>),
+}
+
+impl<'a> Expression<'a> {
+    #[must_use]
+    /// Creates a new binary `Expression`.
+    pub fn binary(
+        left: Expression<'a>,
+        operator: BinaryOperator,
+        right: Expression<'a>,
+    ) -> Expression<'a> {
+        Expression::Binary(Box::new(left), operator, Box::new(right))
+    }
+}
+
+impl fmt::Display for Expression<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Binary(left, operator, right) => write!(f, "({left} {operator} {right})"),
+            Expression::Operand(tokens) => write!(f, "[{}]", tokens.stringify()),
+        }
+    }
+}
+
+/// Order used by the sort operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Ascending order.
+    Ascending,
+    /// Descending order.
+    Descending,
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Order::Ascending => write!(f, "Ascending"),
+            Order::Descending => write!(f, "Descending"),
+        }
+    }
+}
+
+/// Alignment used by the zip and transpose operators for arrays of different
+/// lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Shorter arrays are padded with null.
+    Pad,
+    /// Arrays must have the same length.
+    Strict,
+}
+
+impl fmt::Display for Alignment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Alignment::Pad => write!(f, "Pad"),
+            Alignment::Strict => write!(f, "Strict"),
+        }
+    }
+}
+
+/// Strategy used by the merge operator for arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Arrays are concatenated.
+    Concatenate,
+    /// Arrays are replaced.
+    Replace,
+}
+
+impl fmt::Display for MergeStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeStrategy::Concatenate => write!(f, "Concatenate"),
+            MergeStrategy::Replace => write!(f, "Replace"),
         }
     }
 }
@@ -141,48 +490,177 @@ impl fmt::Display for LensValue<'_> {
 pub enum Token<'a> {
     /// Array index selector.
     ArrayIndexSelector(Vec<Index>),
+    /// Array mixed selector.
+    ArrayMixedSelector(Vec<Selection>),
     /// Array range selector.
     ArrayRangeSelector(Range),
+    /// Average operator
+    AverageOperator,
+    /// Conditional.
+    Conditional(Vec<Lens<'a>>, Vec<Token<'a>>, Vec<Token<'a>>),
+    /// Has index operator
+    HasIndexOperator(Index),
+    /// Has key operator
+    HasKeyOperator(&'a str),
+    /// Join operator
+    JoinOperator(&'a str),
+    /// JSONPath selector, selecting the nodelist of the segments.
+    JsonPathSelector(Vec<PathSegment<'a>>),
     /// Keys operator
     KeyOperator,
+    /// Descendants operator
+    DescendantsOperator,
+    /// Expression.
+    Expression(Expression<'a>),
     /// Flatten operator
     FlattenOperator,
+    /// First operator
+    FirstOperator,
+    /// From JSON operator
+    FromJsonOperator,
+    /// Group by operator
+    GroupByOperator(Vec<Token<'a>>),
     /// Group separator.
     GroupSeparator,
     /// Key selector.
     KeySelector(&'a str),
     /// Lens selector.
     LensSelector(Vec<Lens<'a>>),
+    /// Last operator
+    LastOperator,
+    /// Limit operator
+    LimitOperator(usize),
+    /// Lower operator
+    LowerOperator,
+    /// Max by operator
+    MaxByOperator(Vec<Token<'a>>),
+    /// Max operator
+    MaxOperator,
+    /// Merge operator
+    MergeOperator(Vec<Vec<Token<'a>>>, MergeStrategy),
+    /// Min by operator
+    MinByOperator(Vec<Token<'a>>),
+    /// Min operator
+    MinOperator,
     /// Multi key selector
     MultiKeySelector(Vec<&'a str>),
+    /// Number literal.
+    NumberLiteral(Number),
     /// Object index selector.
     ObjectIndexSelector(Vec<Index>),
+    /// Object mixed selector.
+    ObjectMixedSelector(Vec<Selection>),
+    /// Object key range selector.
+    ObjectKeyRangeSelector(KeyRange<'a>),
     /// Object range selector.
     ObjectRangeSelector(Range),
     /// Pipe in operator
     PipeInOperator,
     /// Pipe out operator
     PipeOutOperator,
+    /// Reference selector, used as a key on objects and as an index on arrays.
+    ReferenceSelector(&'a str),
+    /// Replace operator
+    ReplaceOperator(&'a str, &'a str),
+    /// Skip operator
+    SkipOperator(usize),
+    /// Sort by operator
+    SortByOperator(Vec<Token<'a>>, Order),
+    /// Sort operator
+    SortOperator(Order),
+    /// Split operator
+    SplitOperator(&'a str),
+    /// Sum operator
+    SumOperator,
+    /// To JSON operator
+    ToJsonOperator,
+    /// To number operator
+    ToNumberOperator,
+    /// To string operator
+    ToStringOperator,
+    /// Transpose operator
+    TransposeOperator(Alignment),
+    /// Trim operator
+    TrimOperator,
     /// Truncate operator
     TruncateOperator,
+    /// Type operator
+    TypeOperator,
+    /// Type selector
+    TypeSelector(JsonType),
+    /// Unique by operator
+    UniqueByOperator(Vec<Token<'a>>),
+    /// Unique operator
+    UniqueOperator,
+    /// Upper operator
+    UpperOperator,
+    /// Values operator
+    ValuesOperator,
+    /// Zip object operator
+    ZipObjectOperator(Vec<Vec<Token<'a>>>, Alignment),
+    /// Zip operator
+    ZipOperator(Vec<Vec<Token<'a>>>, Alignment),
 }
 
 impl<'a> Token<'a> {
     fn get_name(&self) -> &'a str {
         match self {
             Token::ArrayIndexSelector(_) => "ArrayIndexSelector",
+            Token::ArrayMixedSelector(_) => "ArrayMixedSelector",
             Token::ArrayRangeSelector(_) => "ArrayRangeSelector",
+            Token::AverageOperator => "AverageOperator",
+            Token::Conditional(..) => "Conditional",
+            Token::DescendantsOperator => "DescendantsOperator",
+            Token::Expression(_) => "Expression",
             Token::FlattenOperator => "FlattenOperator",
+            Token::FirstOperator => "FirstOperator",
+            Token::FromJsonOperator => "FromJsonOperator",
+            Token::GroupByOperator(_) => "GroupByOperator",
             Token::GroupSeparator => "GroupSeparator",
+            Token::HasIndexOperator(_) => "HasIndexOperator",
+            Token::HasKeyOperator(_) => "HasKeyOperator",
+            Token::JoinOperator(_) => "JoinOperator",
+            Token::JsonPathSelector(_) => "JsonPathSelector",
             Token::KeyOperator => "KeyOperator",
             Token::KeySelector(_) => "KeySelector",
             Token::LensSelector(_) => "LensSelector",
+            Token::LastOperator => "LastOperator",
+            Token::LimitOperator(_) => "LimitOperator",
+            Token::LowerOperator => "LowerOperator",
+            Token::MaxByOperator(_) => "MaxByOperator",
+            Token::MaxOperator => "MaxOperator",
+            Token::MergeOperator(..) => "MergeOperator",
+            Token::MinByOperator(_) => "MinByOperator",
+            Token::MinOperator => "MinOperator",
             Token::MultiKeySelector(_) => "MultiKeySelector",
+            Token::NumberLiteral(_) => "NumberLiteral",
             Token::ObjectIndexSelector(_) => "ObjectIndexSelector",
+            Token::ObjectMixedSelector(_) => "ObjectMixedSelector",
+            Token::ObjectKeyRangeSelector(_) => "ObjectKeyRangeSelector",
             Token::ObjectRangeSelector(_) => "ObjectRangeSelector",
             Token::PipeInOperator => "PipeInOperator",
             Token::PipeOutOperator => "PipeOutOperator",
+            Token::ReferenceSelector(_) => "ReferenceSelector",
+            Token::ReplaceOperator(..) => "ReplaceOperator",
+            Token::SkipOperator(_) => "SkipOperator",
+            Token::SortByOperator(..) => "SortByOperator",
+            Token::SortOperator(_) => "SortOperator",
+            Token::SplitOperator(_) => "SplitOperator",
+            Token::SumOperator => "SumOperator",
+            Token::ToJsonOperator => "ToJsonOperator",
+            Token::ToNumberOperator => "ToNumberOperator",
+            Token::ToStringOperator => "ToStringOperator",
+            Token::TransposeOperator(_) => "TransposeOperator",
+            Token::TrimOperator => "TrimOperator",
             Token::TruncateOperator => "TruncateOperator",
+            Token::TypeOperator => "TypeOperator",
+            Token::TypeSelector(_) => "TypeSelector",
+            Token::UniqueByOperator(_) => "UniqueByOperator",
+            Token::UniqueOperator => "UniqueOperator",
+            Token::UpperOperator => "UpperOperator",
+            Token::ValuesOperator => "ValuesOperator",
+            Token::ZipObjectOperator(..) => "ZipObjectOperator",
+            Token::ZipOperator(..) => "ZipOperator",
         }
     }
 }
@@ -198,12 +676,46 @@ impl fmt::Display for Token<'_> {
                     .join(", ");
                 write!(f, "{} [{formatted_indexes}]", self.get_name())
             }
+            Token::ArrayMixedSelector(selections) | Token::ObjectMixedSelector(selections) => {
+                let formatted_selections = selections
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{} [{formatted_selections}]", self.get_name())
+            }
             Token::ArrayRangeSelector(range) | Token::ObjectRangeSelector(range) => {
                 write!(f, "{} {}", self.get_name(), range)
             }
+            Token::ObjectKeyRangeSelector(range) => {
+                write!(f, "{} {}", self.get_name(), range)
+            }
+            Token::Conditional(lenses, consequent, alternative) => {
+                let formatted_lenses = lenses
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "{} [{formatted_lenses}] [{}] [{}]",
+                    self.get_name(),
+                    consequent.stringify(),
+                    alternative.stringify()
+                )
+            }
+            Token::Expression(expression) => {
+                write!(f, "{} {expression}", self.get_name())
+            }
+            Token::NumberLiteral(number) => {
+                write!(f, "{} ({number})", self.get_name())
+            }
             Token::KeySelector(key) => {
                 write!(f, r#"{} "{key}"#, self.get_name())
             }
+            Token::ReferenceSelector(reference) => {
+                write!(f, r#"{} "{reference}""#, self.get_name())
+            }
             Token::LensSelector(lenses) => {
                 let formatted_indexes = lenses
                     .iter()
@@ -212,22 +724,106 @@ impl fmt::Display for Token<'_> {
                     .join(", ");
                 write!(f, "{} [{formatted_indexes}]", self.get_name())
             }
+            Token::JsonPathSelector(segments) => {
+                let formatted_segments = segments
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{} [{formatted_segments}]", self.get_name())
+            }
+            Token::HasIndexOperator(index) => {
+                write!(f, "{} {index}", self.get_name())
+            }
+            Token::HasKeyOperator(key) => {
+                write!(f, r#"{} "{key}""#, self.get_name())
+            }
+            Token::JoinOperator(separator) | Token::SplitOperator(separator) => {
+                write!(f, r#"{} "{separator}""#, self.get_name())
+            }
+            Token::ReplaceOperator(from, to) => {
+                write!(f, r#"{} "{from}" "{to}""#, self.get_name())
+            }
+            Token::MergeOperator(sub_queries, strategy) => {
+                write!(
+                    f,
+                    "{} [{}] {strategy}",
+                    self.get_name(),
+                    stringify_sub_queries(sub_queries)
+                )
+            }
+            Token::ZipObjectOperator(sub_queries, alignment)
+            | Token::ZipOperator(sub_queries, alignment) => {
+                write!(
+                    f,
+                    "{} [{}] {alignment}",
+                    self.get_name(),
+                    stringify_sub_queries(sub_queries)
+                )
+            }
+            Token::TransposeOperator(alignment) => {
+                write!(f, "{} {alignment}", self.get_name())
+            }
             Token::MultiKeySelector(multi_key) => {
                 let formatted_keys = multi_key.join(",");
                 write!(f, "{} {formatted_keys}", self.get_name())
             }
-            Token::FlattenOperator
+            Token::SortByOperator(tokens, order) => {
+                write!(f, "{} [{}] {order}", self.get_name(), tokens.stringify())
+            }
+            Token::LimitOperator(count) | Token::SkipOperator(count) => {
+                write!(f, "{} ({count})", self.get_name())
+            }
+            Token::SortOperator(order) => {
+                write!(f, "{} {order}", self.get_name())
+            }
+            Token::TypeSelector(json_type) => {
+                write!(f, "{} <{json_type}>", self.get_name())
+            }
+            Token::GroupByOperator(tokens)
+            | Token::MaxByOperator(tokens)
+            | Token::MinByOperator(tokens)
+            | Token::UniqueByOperator(tokens) => {
+                write!(f, "{} [{}]", self.get_name(), tokens.stringify())
+            }
+            Token::AverageOperator
+            | Token::DescendantsOperator
+            | Token::FlattenOperator
+            | Token::FirstOperator
+            | Token::FromJsonOperator
             | Token::KeyOperator
             | Token::GroupSeparator
+            | Token::LastOperator
+            | Token::LowerOperator
+            | Token::MaxOperator
+            | Token::MinOperator
             | Token::PipeInOperator
             | Token::PipeOutOperator
-            | Token::TruncateOperator => {
+            | Token::SumOperator
+            | Token::ToJsonOperator
+            | Token::ToNumberOperator
+            | Token::ToStringOperator
+            | Token::TrimOperator
+            | Token::TruncateOperator
+            | Token::TypeOperator
+            | Token::UniqueOperator
+            | Token::UpperOperator
+            | Token::ValuesOperator => {
                 write!(f, "{}", self.get_name())
             }
         }
     }
 }
 
+/// Returns a stringified version of a slice of sub-queries.
+fn stringify_sub_queries(sub_queries: &[Vec<Token>]) -> String {
+    sub_queries
+        .iter()
+        .map(|tokens| format!("[{}]", tokens.stringify()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 /// Trait used to expose custom display methods.
 pub trait View {
     /// Returns a stringified version of `self`.
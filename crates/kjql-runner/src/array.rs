@@ -1,21 +1,42 @@
-use std::num::NonZeroUsize;
+use std::cmp::Ordering;
 
+use indexmap::IndexMap;
 use kjql_parser::tokens::{
+    Alignment,
     Index,
+    JsonType,
     Lens,
     LensValue,
+    Order,
     Range,
+    Selection,
     Token,
+    View,
 };
 use rayon::prelude::*;
 use serde_json::{
+    Map,
+    Number,
     Value,
     json,
 };
 
 use crate::{
     errors::KjqlRunnerError,
-    runner::group_runner,
+    runner::{
+        RunnerOptions,
+        group_runner_with_options,
+    },
+    value::{
+        apply_order,
+        as_integer,
+        as_number,
+        compare_values,
+        get_json_type,
+        get_range_boundaries,
+        integer_to_number,
+        number_from_f64,
+    },
 };
 
 /// Takes a mutable reference of JSON `Value` and returns a reference of a
@@ -44,9 +65,41 @@ fn get_array_index(index: Index, json: &Value) -> Result<Value, KjqlRunnerError>
     }
 }
 
-/// Takes a slice of `Index` and a reference of a JSON `Value`.
+/// Takes an `Index` and a reference of a JSON `Value`.
+/// Returns `true` as a JSON `Value` if the value is an array containing the
+/// index, `false` otherwise.
+pub(crate) fn get_array_has_index(index: Index, json: &Value) -> Value {
+    let num: usize = index.into();
+
+    json!(json.as_array().is_some_and(|array| num < array.len()))
+}
+
+/// Takes a slice of `Index`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
 /// Returns a refernece of a JSON `Value` or an error.
-pub(crate) fn get_array_indexes(indexes: &[Index], json: &Value) -> Result<Value, KjqlRunnerError> {
+/// In lenient mode, missing indexes are skipped and a single missing index
+/// returns `null`.
+pub(crate) fn get_array_indexes(
+    indexes: &[Index],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    if options.lenient {
+        if !json.is_array() {
+            return Err(KjqlRunnerError::InvalidArrayError(json.clone()));
+        }
+        if indexes.len() == 1 {
+            return Ok(get_array_index(indexes[0], json).unwrap_or(Value::Null));
+        }
+
+        return Ok(json!(
+            indexes
+                .iter()
+                .filter_map(|index| get_array_index(*index, json).ok())
+                .collect::<Vec<Value>>()
+        ));
+    }
+
     if indexes.len() == 1 {
         return get_array_index(indexes[0], json);
     }
@@ -59,28 +112,20 @@ pub(crate) fn get_array_indexes(indexes: &[Index], json: &Value) -> Result<Value
     Ok(json!(values))
 }
 
-/// Takes a reference of a `Range` and a mutable reference of a JSON `Value`.
+/// Takes a reference of a `Range`, a mutable reference of a JSON `Value` and
+/// the `RunnerOptions`.
 /// Returns a JSON `Value` or an error.
-pub(crate) fn get_array_range(range: &Range, json: &mut Value) -> Result<Value, KjqlRunnerError> {
-    let array = as_array_mut(json)?;
-
-    if array.is_empty() {
+/// In lenient mode, the boundaries are clamped to the length of the array.
+pub(crate) fn get_array_range(
+    range: &Range,
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let len = as_array_mut(json)?.len();
+    let Some((start, end)) = get_range_boundaries(range, json, len, options)? else {
         return Ok(json!([]));
-    }
-
-    let len = array.len();
-    // Array's length can't be zero so we can safely unwrap here.
-    let non_zero_len = NonZeroUsize::new(len).unwrap();
-    let (start, end) = range.to_boundaries(non_zero_len);
-
-    // Out of bounds
-    if start + 1 > len || end + 1 > len {
-        return Err(KjqlRunnerError::RangeOutOfBoundsError {
-            start,
-            end,
-            parent: json.clone(),
-        });
-    }
+    };
+    let array = as_array_mut(json)?;
 
     let is_natural_order = start < end;
     let result = if is_natural_order {
@@ -96,6 +141,94 @@ pub(crate) fn get_array_range(range: &Range, json: &mut Value) -> Result<Value,
     Ok(json!(result))
 }
 
+/// Takes a slice of `Selection`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns the selected elements concatenated in the order of the selections
+/// as a JSON `Value` or an error.
+pub(crate) fn get_array_selections(
+    selections: &[Selection],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let Some(array) = json.as_array() else {
+        return Err(KjqlRunnerError::InvalidArrayError(json.clone()));
+    };
+    let len = array.len();
+
+    let result = selections
+        .iter()
+        .try_fold(vec![], |mut acc: Vec<Value>, selection| {
+            match selection {
+                Selection::Index(index) => match get_array_index(*index, json) {
+                    Ok(value) => acc.push(value),
+                    Err(_) if options.lenient => {}
+                    Err(error) => return Err(error),
+                },
+                Selection::IndexFromEnd(index) => match index.from_end(len) {
+                    Some(index) => acc.push(get_array_index(index, json)?),
+                    None if options.lenient => {}
+                    None => {
+                        return Err(KjqlRunnerError::IndexFromEndOutOfBoundsError {
+                            index: (*index).into(),
+                            parent: json.clone(),
+                        });
+                    }
+                },
+                Selection::Range(range) => {
+                    if let Some((start, end)) = get_range_boundaries(range, json, len, options)? {
+                        if start < end {
+                            acc.extend(array[start..=end].iter().cloned());
+                        } else {
+                            acc.extend(array[end..=start].iter().rev().cloned());
+                        }
+                    }
+                }
+            }
+
+            Ok::<Vec<Value>, KjqlRunnerError>(acc)
+        })?;
+
+    Ok(json!(result))
+}
+
+/// Takes a count and a mutable reference of a JSON `Value`.
+/// Returns at most the first `count` elements of the array as a JSON `Value`
+/// or an error.
+pub(crate) fn get_limited_array(count: usize, json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    array.truncate(count);
+
+    Ok(json!(array))
+}
+
+/// Takes a count and a mutable reference of a JSON `Value`.
+/// Returns the array without its first `count` elements as a JSON `Value` or
+/// an error.
+pub(crate) fn get_skipped_array(count: usize, json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    array.drain(..count.min(array.len()));
+
+    Ok(json!(array))
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns the first element of the array or null if the array is empty as a
+/// JSON `Value` or an error.
+pub(crate) fn get_first_element(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    Ok(array.first().cloned().unwrap_or(Value::Null))
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns the last element of the array or null if the array is empty as a
+/// JSON `Value` or an error.
+pub(crate) fn get_last_element(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    Ok(array.last().cloned().unwrap_or(Value::Null))
+}
+
 /// Takes a reference of a JSON `Value`.
 /// Returns a flattend array as a JSON `Value or an error.
 /// Note the runner checks that the input is a JSON array.
@@ -122,11 +255,41 @@ pub(crate) fn get_flattened_array(json: &Value) -> Result<Value, KjqlRunnerError
     Ok(json!(result))
 }
 
-/// Takes a slice of `Lens` and a mutable reference of a JSON `Value`.
+/// Takes a slice of `Lens`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns `true` if any of the lenses matches the value.
+pub(crate) fn is_matching_lenses(lenses: &[Lens], json: &Value, options: RunnerOptions) -> bool {
+    lenses.iter().any(|lens| {
+        let (tokens, value) = lens.get();
+
+        let tokens: Vec<&Token> = tokens.iter().collect();
+        let result = group_runner_with_options(&tokens, json, options);
+        if let Ok(current_value) = result {
+            match value {
+                Some(LensValue::Bool(boolean)) => {
+                    current_value.is_boolean() && current_value.as_bool().unwrap() == boolean
+                }
+                Some(LensValue::Exists) | None => true,
+                Some(LensValue::Null) => current_value.is_null(),
+                Some(LensValue::Number(value)) => {
+                    current_value.is_u64() && current_value.as_u64().unwrap() == value as u64
+                }
+                Some(LensValue::String(value)) => current_value == value,
+                Some(LensValue::Type(json_type)) => get_json_type(&current_value) == json_type,
+            }
+        } else {
+            false
+        }
+    })
+}
+
+/// Takes a slice of `Lens`, a mutable reference of a JSON `Value` and the
+/// `RunnerOptions`.
 /// Returns a JSON `Value` or an error.
 pub(crate) fn get_array_lenses(
     lenses: &[Lens],
     json: &mut Value,
+    options: RunnerOptions,
 ) -> Result<Value, KjqlRunnerError> {
     let array = as_array_mut(json)?;
 
@@ -137,29 +300,7 @@ pub(crate) fn get_array_lenses(
     let result = array
         .par_iter()
         .try_fold_with(Vec::new(), |mut acc: Vec<Value>, inner_value| {
-            if lenses.iter().any(|lens| {
-                let (tokens, value) = lens.get();
-
-                let tokens: Vec<&Token> = tokens.iter().collect();
-                let result = group_runner(&tokens, inner_value);
-                if let Ok(current_value) = result {
-                    match value {
-                        Some(LensValue::Bool(boolean)) => {
-                            current_value.is_boolean()
-                                && current_value.as_bool().unwrap() == boolean
-                        }
-                        Some(LensValue::Null) => current_value.is_null(),
-                        Some(LensValue::Number(value)) => {
-                            current_value.is_u64()
-                                && current_value.as_u64().unwrap() == value as u64
-                        }
-                        Some(LensValue::String(value)) => current_value == value,
-                        None => true,
-                    }
-                } else {
-                    false
-                }
-            }) {
+            if is_matching_lenses(lenses, inner_value, options) {
                 acc.push(inner_value.clone());
             }
 
@@ -174,6 +315,24 @@ pub(crate) fn get_array_lenses(
     Ok(json!(result))
 }
 
+/// Takes a JSON type and a mutable reference of a JSON `Value`.
+/// Returns the elements of the array matching the type as a JSON `Value` or
+/// an error.
+pub(crate) fn get_array_of_type(
+    json_type: JsonType,
+    json: &mut Value,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    let result = array
+        .par_iter()
+        .filter(|value| get_json_type(value) == json_type)
+        .cloned()
+        .collect::<Vec<Value>>();
+
+    Ok(json!(result))
+}
+
 /// Takes a reference of a JSON `Value`
 /// Converts the original array as indexes and return a JSON `Value` or an error.
 /// Not: the runner checks that the input is a JSON array.
@@ -194,26 +353,506 @@ pub(crate) fn get_array_as_indexes(json: &Value) -> Result<Value, KjqlRunnerErro
     Ok(json!(result))
 }
 
+/// Takes an `Order` and a mutable reference of a JSON `Value`.
+/// Returns a stable sorted array following the JSON type ordering as a JSON
+/// `Value` or an error.
+pub(crate) fn get_sorted_array(order: Order, json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    array.par_sort_by(|a, b| apply_order(order, compare_values(a, b)));
+
+    Ok(json!(array))
+}
+
+/// Takes a slice of `Token` used as a sub-query, a slice of JSON `Value` and
+/// the `RunnerOptions`.
+/// Returns the results of the sub-query applied on each element or an error.
+fn get_sub_query_values(
+    tokens: &[Token],
+    array: &[Value],
+    options: RunnerOptions,
+) -> Result<Vec<Value>, KjqlRunnerError> {
+    let tokens: Vec<&Token> = tokens.iter().collect();
+
+    array
+        .par_iter()
+        .try_fold_with(Vec::new(), |mut acc: Vec<Value>, inner_value| {
+            acc.push(group_runner_with_options(&tokens, inner_value, options)?);
+
+            Ok::<Vec<Value>, KjqlRunnerError>(acc)
+        })
+        .try_reduce(Vec::new, |mut a, b| {
+            a.extend(b);
+
+            Ok(a)
+        })
+}
+
+/// Takes a slice of `Token` used as a sub-query, an `Order` and a mutable
+/// reference of a JSON `Value`.
+/// Returns a stable sorted array based on the result of the sub-query applied
+/// on each element as a JSON `Value` or an error.
+pub(crate) fn get_sorted_array_by(
+    tokens: &[Token],
+    order: Order,
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    let keys = get_sub_query_values(tokens, array, options)?;
+
+    let mut result: Vec<(Value, Value)> = keys.into_iter().zip(array.iter().cloned()).collect();
+
+    result.par_sort_by(|(a, _), (b, _)| apply_order(order, compare_values(a, b)));
+
+    Ok(json!(
+        result
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect::<Vec<Value>>()
+    ))
+}
+
+/// Takes a slice of JSON `Value` used as keys.
+/// Returns the indexes of the first occurrence of each distinct key in their
+/// original order.
+fn get_first_occurrences(keys: &[Value]) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..keys.len()).collect();
+
+    // The sort is stable, the first index of a chunk is the first occurrence.
+    indexes.par_sort_by(|a, b| compare_values(&keys[*a], &keys[*b]));
+
+    let mut result: Vec<usize> = indexes
+        .chunk_by(|a, b| compare_values(&keys[*a], &keys[*b]).is_eq())
+        .map(|chunk| chunk[0])
+        .collect();
+
+    // Restore the original order.
+    result.par_sort_unstable();
+
+    result
+}
+
+/// Takes two slices of JSON `Value` and a predicate telling whether a value
+/// of the left slice found in the right slice is kept.
+/// Returns the distinct kept values of the left slice in their original order.
+fn get_set_values(left: &[Value], right: &[Value], keep_found: bool) -> Vec<Value> {
+    let kept = left
+        .par_iter()
+        .filter(|value| {
+            right
+                .iter()
+                .any(|other| compare_values(value, other).is_eq())
+                == keep_found
+        })
+        .cloned()
+        .collect::<Vec<Value>>();
+
+    get_first_occurrences(&kept)
+        .into_iter()
+        .map(|index| kept[index].clone())
+        .collect()
+}
+
+/// Takes two slices of JSON `Value`.
+/// Returns the distinct values of the left array missing from the right one as
+/// a JSON `Value`.
+pub(crate) fn get_array_difference(left: &[Value], right: &[Value]) -> Value {
+    json!(get_set_values(left, right, false))
+}
+
+/// Takes two slices of JSON `Value`.
+/// Returns the distinct values of the left array found in the right one as a
+/// JSON `Value`.
+pub(crate) fn get_array_intersection(left: &[Value], right: &[Value]) -> Value {
+    json!(get_set_values(left, right, true))
+}
+
+/// Takes two slices of JSON `Value`.
+/// Returns the distinct values of both arrays, left ones first, as a JSON
+/// `Value`.
+pub(crate) fn get_array_union(left: &[Value], right: &[Value]) -> Value {
+    let values = [left, right].concat();
+
+    json!(
+        get_first_occurrences(&values)
+            .into_iter()
+            .map(|index| values[index].clone())
+            .collect::<Vec<Value>>()
+    )
+}
+
+/// Takes a slice of arrays and an `Alignment`.
+/// Returns the transposed arrays or an error if the arrays have different
+/// lengths and are not padded.
+fn transpose_arrays(
+    arrays: &[&Vec<Value>],
+    alignment: Alignment,
+) -> Result<Vec<Vec<Value>>, KjqlRunnerError> {
+    let lengths: Vec<usize> = arrays.iter().map(|array| array.len()).collect();
+    let max_length = lengths.iter().copied().max().unwrap_or(0);
+
+    if alignment == Alignment::Strict && lengths.iter().any(|length| *length != max_length) {
+        return Err(KjqlRunnerError::LengthMismatchError { lengths });
+    }
+
+    Ok((0..max_length)
+        .into_par_iter()
+        .map(|index| {
+            arrays
+                .iter()
+                .map(|array| array.get(index).cloned().unwrap_or(Value::Null))
+                .collect()
+        })
+        .collect())
+}
+
+/// Takes a slice of sub-queries, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns the arrays resulting from the sub-queries or an error.
+fn get_sub_queries_arrays(
+    sub_queries: &[Vec<Token>],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Vec<Value>, KjqlRunnerError> {
+    sub_queries
+        .par_iter()
+        .map(|tokens| {
+            let tokens: Vec<&Token> = tokens.iter().collect();
+            let value = group_runner_with_options(&tokens, json, options)?;
+
+            if value.is_array() {
+                Ok(value)
+            } else {
+                Err(KjqlRunnerError::InvalidArrayError(value))
+            }
+        })
+        .collect()
+}
+
+/// Takes a slice of sub-queries, an `Alignment`, a reference of a JSON `Value`
+/// and the `RunnerOptions`.
+/// Returns the arrays of the sub-queries zipped into an array of tuples as a
+/// JSON `Value` or an error.
+pub(crate) fn get_zipped_arrays(
+    sub_queries: &[Vec<Token>],
+    alignment: Alignment,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let arrays = get_sub_queries_arrays(sub_queries, json, options)?;
+    // We can safely unwrap here since the values are arrays.
+    let arrays: Vec<&Vec<Value>> = arrays
+        .iter()
+        .map(|array| array.as_array().unwrap())
+        .collect();
+
+    Ok(json!(transpose_arrays(&arrays, alignment)?))
+}
+
+/// Takes a slice of sub-queries, an `Alignment`, a reference of a JSON `Value`
+/// and the `RunnerOptions`.
+/// Returns the arrays of the sub-queries zipped into an array of objects as a
+/// JSON `Value` or an error.
+/// Each sub-query is keyed by its last key selector, or by its stringified
+/// tokens otherwise.
+pub(crate) fn get_zipped_objects(
+    sub_queries: &[Vec<Token>],
+    alignment: Alignment,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let keys: Vec<String> = sub_queries
+        .iter()
+        .map(|tokens| match tokens.last() {
+            Some(Token::KeySelector(key)) => (*key).to_string(),
+            _ => tokens.stringify(),
+        })
+        .collect();
+    let arrays = get_sub_queries_arrays(sub_queries, json, options)?;
+    // We can safely unwrap here since the values are arrays.
+    let arrays: Vec<&Vec<Value>> = arrays
+        .iter()
+        .map(|array| array.as_array().unwrap())
+        .collect();
+
+    Ok(json!(
+        transpose_arrays(&arrays, alignment)?
+            .into_iter()
+            .map(|row| keys
+                .iter()
+                .cloned()
+                .zip(row)
+                .collect::<Map<String, Value>>())
+            .collect::<Vec<Map<String, Value>>>()
+    ))
+}
+
+/// Takes an `Alignment` and a mutable reference of a JSON `Value`.
+/// Returns the transposed array of arrays as a JSON `Value` or an error.
+pub(crate) fn get_transposed_array(
+    alignment: Alignment,
+    json: &mut Value,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    let arrays = array
+        .iter()
+        .map(|value| {
+            value
+                .as_array()
+                .ok_or_else(|| KjqlRunnerError::InvalidArrayError(value.clone()))
+        })
+        .collect::<Result<Vec<&Vec<Value>>, KjqlRunnerError>>()?;
+
+    Ok(json!(transpose_arrays(&arrays, alignment)?))
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns an array without duplicated values as a JSON `Value` or an error.
+/// The first occurrence of a value is kept along with the original order.
+pub(crate) fn get_unique_array(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    Ok(json!(
+        get_first_occurrences(array)
+            .into_iter()
+            .map(|index| array[index].clone())
+            .collect::<Vec<Value>>()
+    ))
+}
+
+/// Takes a slice of `Token` used as a sub-query and a mutable reference of a
+/// JSON `Value`.
+/// Returns an array without elements sharing the same sub-query result as a
+/// JSON `Value` or an error.
+/// The first occurrence of an element is kept along with the original order.
+pub(crate) fn get_unique_array_by(
+    tokens: &[Token],
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    let keys = get_sub_query_values(tokens, array, options)?;
+
+    Ok(json!(
+        get_first_occurrences(&keys)
+            .into_iter()
+            .map(|index| array[index].clone())
+            .collect::<Vec<Value>>()
+    ))
+}
+
+/// Takes a slice of `Token` used as a sub-query and a mutable reference of a
+/// JSON `Value`.
+/// Returns an object of arrays keyed by the stringified sub-query results as a
+/// JSON `Value` or an error.
+/// Keys and elements are kept in their first-seen order.
+pub(crate) fn get_array_groups_by(
+    tokens: &[Token],
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    let keys = get_sub_query_values(tokens, array, options)?;
+
+    let groups = keys.into_iter().zip(array.iter()).fold(
+        IndexMap::new(),
+        |mut acc: IndexMap<String, Vec<Value>>, (key, value)| {
+            let key = match key {
+                Value::String(string) => string,
+                _ => key.to_string(),
+            };
+
+            acc.entry(key).or_default().push(value.clone());
+            acc
+        },
+    );
+
+    let len = groups.len();
+    let new_map = groups
+        .into_iter()
+        .fold(Map::with_capacity(len), |mut acc, (key, values)| {
+            acc.insert(key, json!(values));
+            acc
+        });
+
+    Ok(json!(new_map))
+}
+
+/// Takes a slice of JSON `Value`.
+/// Returns the sum of the numbers or an error.
+/// Integers are summed exactly and in parallel as `i128`, the result being
+/// the same for any grouping. The sum falls back on floats added sequentially
+/// in the array order if any number is a float or if the sum overflows.
+fn sum_numbers(array: &[Value]) -> Result<Number, KjqlRunnerError> {
+    let numbers = array
+        .par_iter()
+        .map(as_number)
+        .collect::<Result<Vec<&Number>, KjqlRunnerError>>()?;
+
+    let integer_sum = numbers
+        .par_iter()
+        .map(|number| as_integer(number))
+        .try_reduce(|| 0, i128::checked_add);
+
+    if let Some(number) = integer_sum.and_then(integer_to_number) {
+        return Ok(number);
+    }
+
+    // A JSON number is always representable as a `f64`.
+    number_from_f64(numbers.iter().map(|number| number.as_f64().unwrap()).sum())
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns the sum of an array of numbers as a JSON `Value` or an error.
+/// The sum of an empty array is 0.
+pub(crate) fn get_array_sum(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    Ok(json!(sum_numbers(array)?))
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns the average of an array of numbers as a JSON `Value` or an error.
+pub(crate) fn get_array_average(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    if array.is_empty() {
+        return Err(KjqlRunnerError::EmptyArrayError("Average".to_string()));
+    }
+
+    let sum = sum_numbers(array)?;
+
+    // A JSON number is always representable as a `f64`.
+    Ok(json!(number_from_f64(
+        sum.as_f64().unwrap() / array.len() as f64
+    )?))
+}
+
+/// Internal utility for the min and max operators.
+/// Takes the name of the operator, the numeric keys used for the comparison,
+/// the elements of the array and the `Ordering` to retain.
+/// Returns the first element with the extreme key or an error.
+fn get_extremum(
+    operator: &str,
+    keys: &[Value],
+    array: &[Value],
+    ordering: Ordering,
+) -> Result<Value, KjqlRunnerError> {
+    let (index, _) = keys
+        .par_iter()
+        .enumerate()
+        .map(|(index, key)| as_number(key).map(|_| (index, key)))
+        .try_reduce_with(|a, b| {
+            // Keep the first occurrence on equality.
+            if compare_values(b.1, a.1) == ordering {
+                Ok(b)
+            } else {
+                Ok(a)
+            }
+        })
+        .ok_or_else(|| KjqlRunnerError::EmptyArrayError(operator.to_string()))??;
+
+    Ok(array[index].clone())
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns the minimum of an array of numbers as a JSON `Value` or an error.
+pub(crate) fn get_array_min(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    get_extremum("Min", array, array, Ordering::Less)
+}
+
+/// Takes a mutable reference of a JSON `Value`.
+/// Returns the maximum of an array of numbers as a JSON `Value` or an error.
+pub(crate) fn get_array_max(json: &mut Value) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+
+    get_extremum("Max", array, array, Ordering::Greater)
+}
+
+/// Takes a slice of `Token` used as a sub-query and a mutable reference of a
+/// JSON `Value`.
+/// Returns the first element with the minimum numeric sub-query result as a
+/// JSON `Value` or an error.
+pub(crate) fn get_array_min_by(
+    tokens: &[Token],
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    let keys = get_sub_query_values(tokens, array, options)?;
+
+    get_extremum("Min by", &keys, array, Ordering::Less)
+}
+
+/// Takes a slice of `Token` used as a sub-query and a mutable reference of a
+/// JSON `Value`.
+/// Returns the first element with the maximum numeric sub-query result as a
+/// JSON `Value` or an error.
+pub(crate) fn get_array_max_by(
+    tokens: &[Token],
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let array = as_array_mut(json)?;
+    let keys = get_sub_query_values(tokens, array, options)?;
+
+    get_extremum("Max by", &keys, array, Ordering::Greater)
+}
+
 #[cfg(test)]
 mod tests {
     use kjql_parser::tokens::{
+        Alignment,
         Index,
+        JsonType,
         Lens,
         LensValue,
+        Order,
         Range,
+        Selection,
         Token,
     };
     use serde_json::json;
 
     use super::{
         get_array_as_indexes,
+        get_array_average,
+        get_array_difference,
+        get_array_groups_by,
+        get_array_has_index,
         get_array_index,
         get_array_indexes,
+        get_array_intersection,
         get_array_lenses,
+        get_array_max,
+        get_array_max_by,
+        get_array_min,
+        get_array_min_by,
+        get_array_of_type,
         get_array_range,
+        get_array_selections,
+        get_array_sum,
+        get_array_union,
+        get_first_element,
         get_flattened_array,
+        get_last_element,
+        get_limited_array,
+        get_skipped_array,
+        get_sorted_array,
+        get_sorted_array_by,
+        get_transposed_array,
+        get_unique_array,
+        get_unique_array_by,
+        get_zipped_arrays,
+        get_zipped_objects,
+    };
+    use crate::{
+        errors::KjqlRunnerError,
+        runner::RunnerOptions,
     };
-    use crate::errors::KjqlRunnerError;
 
     #[test]
     fn check_get_array_index() {
@@ -233,13 +872,24 @@ mod tests {
     fn check_get_array_indexes() {
         let value = json!(["a", "b", "c"]);
 
-        assert_eq!(Ok(json!("a")), get_array_indexes(&[Index::new(0)], &value));
         assert_eq!(
-            get_array_indexes(&[Index::new(0), Index::new(2)], &value),
+            Ok(json!("a")),
+            get_array_indexes(&[Index::new(0)], &value, RunnerOptions::default())
+        );
+        assert_eq!(
+            get_array_indexes(
+                &[Index::new(0), Index::new(2)],
+                &value,
+                RunnerOptions::default()
+            ),
             Ok(json!(["a", "c"]))
         );
         assert_eq!(
-            get_array_indexes(&[Index::new(0), Index::new(3)], &value),
+            get_array_indexes(
+                &[Index::new(0), Index::new(3)],
+                &value,
+                RunnerOptions::default()
+            ),
             Err(KjqlRunnerError::IndexOutOfBoundsError {
                 index: 3,
                 parent: value
@@ -260,41 +910,57 @@ mod tests {
         assert_eq!(
             get_array_range(
                 &Range::new(Some(Index::new(0)), Some(Index::new(2))),
-                &mut json!([])
+                &mut json!([]),
+                RunnerOptions::default()
             ),
             Ok(json!([]))
         );
         assert_eq!(
             get_array_range(
                 &Range::new(Some(Index::new(0)), Some(Index::new(2))),
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!(["a", "b", "c"]))
         );
         assert_eq!(
             get_array_range(
                 &Range::new(Some(Index::new(2)), Some(Index::new(0))),
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!(["c", "b", "a"]))
         );
         assert_eq!(
             get_array_range(
                 &Range::new(Some(Index::new(0)), Some(Index::new(0))),
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!(["a"]))
         );
         assert_eq!(
-            get_array_range(&Range::new(None, Some(Index::new(4))), &mut value.clone()),
+            get_array_range(
+                &Range::new(None, Some(Index::new(4))),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Ok(json!(["a", "b", "c", "d", "e"]))
         );
         assert_eq!(
-            get_array_range(&Range::new(Some(Index::new(4)), None), &mut value.clone()),
+            get_array_range(
+                &Range::new(Some(Index::new(4)), None),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Ok(json!(["e"]))
         );
         assert_eq!(
-            get_array_range(&Range::new(None, Some(Index::new(5))), &mut value.clone()),
+            get_array_range(
+                &Range::new(None, Some(Index::new(5))),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Err(KjqlRunnerError::RangeOutOfBoundsError {
                 start: 0,
                 end: 5,
@@ -304,11 +970,136 @@ mod tests {
 
         let value = json!(1);
         assert_eq!(
-            get_array_range(&Range::new(None, Some(Index::new(5))), &mut value.clone()),
+            get_array_range(
+                &Range::new(None, Some(Index::new(5))),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Err(KjqlRunnerError::InvalidArrayError(value))
         );
     }
 
+    #[test]
+    fn check_get_array_selections() {
+        let value = json!(["a", "b", "c", "d", "e"]);
+        let selections = [
+            Selection::Index(Index::new(4)),
+            Selection::Range(Range::new(Some(Index::new(1)), Some(Index::new(2)))),
+            Selection::Index(Index::new(0)),
+        ];
+
+        assert_eq!(
+            get_array_selections(&selections, &value, RunnerOptions::default()),
+            Ok(json!(["e", "b", "c", "a"]))
+        );
+        assert_eq!(
+            get_array_selections(
+                &[
+                    Selection::Index(Index::new(0)),
+                    Selection::Range(Range::new(Some(Index::new(2)), Some(Index::new(3)))),
+                    Selection::IndexFromEnd(Index::new(1)),
+                ],
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!(["a", "c", "d", "e"]))
+        );
+        assert_eq!(
+            get_array_selections(
+                &[Selection::IndexFromEnd(Index::new(6))],
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::IndexFromEndOutOfBoundsError {
+                index: 6,
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_array_selections(
+                &[
+                    Selection::IndexFromEnd(Index::new(6)),
+                    Selection::IndexFromEnd(Index::new(5)),
+                ],
+                &value,
+                RunnerOptions { lenient: true }
+            ),
+            Ok(json!(["a"]))
+        );
+        assert_eq!(
+            get_array_selections(
+                &[
+                    Selection::Index(Index::new(7)),
+                    Selection::Range(Range::new(Some(Index::new(3)), None)),
+                ],
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::IndexOutOfBoundsError {
+                index: 7,
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_array_selections(
+                &[
+                    Selection::Index(Index::new(7)),
+                    Selection::Range(Range::new(Some(Index::new(3)), None)),
+                ],
+                &value,
+                RunnerOptions { lenient: true }
+            ),
+            Ok(json!(["d", "e"]))
+        );
+        assert_eq!(
+            get_array_selections(&selections, &json!({}), RunnerOptions::default()),
+            Err(KjqlRunnerError::InvalidArrayError(json!({})))
+        );
+    }
+
+    #[test]
+    fn check_get_array_lenient() {
+        let value = json!(["a", "b", "c"]);
+        let options = RunnerOptions { lenient: true };
+
+        assert_eq!(
+            get_array_indexes(&[Index::new(5)], &value, options),
+            Ok(json!(null))
+        );
+        assert_eq!(
+            get_array_indexes(
+                &[Index::new(0), Index::new(5), Index::new(2)],
+                &value,
+                options
+            ),
+            Ok(json!(["a", "c"]))
+        );
+        assert_eq!(
+            get_array_range(
+                &Range::new(Some(Index::new(1)), Some(Index::new(10))),
+                &mut value.clone(),
+                options
+            ),
+            Ok(json!(["b", "c"]))
+        );
+        assert_eq!(
+            get_array_range(
+                &Range::new(Some(Index::new(10)), Some(Index::new(0))),
+                &mut value.clone(),
+                options
+            ),
+            Ok(json!(["c", "b", "a"]))
+        );
+        assert_eq!(
+            get_array_range(
+                &Range::new(Some(Index::new(5)), Some(Index::new(10))),
+                &mut value.clone(),
+                options
+            ),
+            Ok(json!([]))
+        );
+    }
+
     #[test]
     fn check_get_flattened_array() {
         assert_eq!(
@@ -335,14 +1126,16 @@ mod tests {
         assert_eq!(
             get_array_lenses(
                 &[Lens::new(&[Token::KeySelector("a")], None)],
-                &mut json!([])
+                &mut json!([]),
+                RunnerOptions::default()
             ),
             Ok(json!([]))
         );
         assert_eq!(
             get_array_lenses(
                 &[Lens::new(&[Token::KeySelector("a")], None)],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([
                 { "a": 1, "b": 2 },
@@ -357,7 +1150,8 @@ mod tests {
                     &[Token::KeySelector("a")],
                     Some(LensValue::Number(1))
                 )],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([{ "a": 1, "b": 2 }]))
         );
@@ -367,7 +1161,8 @@ mod tests {
                     Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(1))),
                     Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(2))),
                 ],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([
                 { "a": 1, "b": 2 },
@@ -382,7 +1177,8 @@ mod tests {
                     Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(1))),
                     Lens::new(&[Token::KeySelector("b")], Some(LensValue::Number(2))),
                 ],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([
                 { "a": 1, "b": 2 },
@@ -394,7 +1190,8 @@ mod tests {
                     Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(1))),
                     Lens::new(&[Token::KeySelector("b")], Some(LensValue::String("some"))),
                 ],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([
                 { "a": 1, "b": 2 },
@@ -407,7 +1204,8 @@ mod tests {
                     Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(1))),
                     Lens::new(&[Token::KeySelector("b")], Some(LensValue::Bool(true))),
                 ],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([
                 { "a": 1, "b": 2 },
@@ -417,15 +1215,475 @@ mod tests {
         assert_eq!(
             get_array_lenses(
                 &[
-                    Lens::new(&vec![Token::KeySelector("a")], Some(LensValue::Number(1))),
-                    Lens::new(&vec![Token::KeySelector("b")], Some(LensValue::Null)),
+                    Lens::new(&[Token::KeySelector("a")], Some(LensValue::Number(1))),
+                    Lens::new(&[Token::KeySelector("b")], Some(LensValue::Null)),
                 ],
-                &mut value
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!([
                 { "a": 1, "b": 2 },
                 { "a": 2, "b": null },
             ]))
         );
+        assert_eq!(
+            get_array_lenses(
+                &[Lens::new(
+                    &[Token::KeySelector("b")],
+                    Some(LensValue::Type(JsonType::String))
+                )],
+                &mut value,
+                RunnerOptions::default()
+            ),
+            Ok(json!([{ "a": 2, "b": "some" }]))
+        );
+    }
+
+    #[test]
+    fn check_get_array_slices() {
+        let value = json!([1, 2, 3]);
+
+        assert_eq!(get_limited_array(2, &mut value.clone()), Ok(json!([1, 2])));
+        assert_eq!(
+            get_limited_array(10, &mut value.clone()),
+            Ok(json!([1, 2, 3]))
+        );
+        assert_eq!(get_skipped_array(1, &mut value.clone()), Ok(json!([2, 3])));
+        assert_eq!(get_skipped_array(10, &mut value.clone()), Ok(json!([])));
+        assert_eq!(get_first_element(&mut value.clone()), Ok(json!(1)));
+        assert_eq!(get_last_element(&mut value.clone()), Ok(json!(3)));
+        assert_eq!(get_first_element(&mut json!([])), Ok(json!(null)));
+        assert_eq!(get_last_element(&mut json!([])), Ok(json!(null)));
+        assert_eq!(
+            get_limited_array(1, &mut json!({ "a": 1 })),
+            Err(KjqlRunnerError::InvalidArrayError(json!({ "a": 1 })))
+        );
+    }
+
+    #[test]
+    fn check_get_zipped_arrays() {
+        let value = json!({ "ts": [1, 2, 3], "value": ["a", "b", "c"], "short": [true] });
+        let sub_queries = |keys: &[&'static str]| {
+            keys.iter()
+                .map(|key| vec![Token::KeySelector(key)])
+                .collect::<Vec<Vec<Token>>>()
+        };
+
+        assert_eq!(
+            get_zipped_arrays(
+                &sub_queries(&["ts", "value"]),
+                Alignment::Strict,
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!([[1, "a"], [2, "b"], [3, "c"]]))
+        );
+        assert_eq!(
+            get_zipped_arrays(
+                &sub_queries(&["short", "ts"]),
+                Alignment::Strict,
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::LengthMismatchError {
+                lengths: vec![1, 3]
+            })
+        );
+        assert_eq!(
+            get_zipped_arrays(
+                &sub_queries(&["short", "ts"]),
+                Alignment::Pad,
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!([[true, 1], [null, 2], [null, 3]]))
+        );
+        assert_eq!(
+            get_zipped_objects(
+                &sub_queries(&["ts", "value"]),
+                Alignment::Strict,
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!([
+                { "ts": 1, "value": "a" },
+                { "ts": 2, "value": "b" },
+                { "ts": 3, "value": "c" },
+            ]))
+        );
+        assert_eq!(
+            get_zipped_arrays(
+                &sub_queries(&["ts", "ts"]),
+                Alignment::Strict,
+                &json!({ "ts": 1 }),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidArrayError(json!(1)))
+        );
+    }
+
+    #[test]
+    fn check_get_transposed_array() {
+        assert_eq!(
+            get_transposed_array(Alignment::Strict, &mut json!([[1, 2], [3, 4], [5, 6]])),
+            Ok(json!([[1, 3, 5], [2, 4, 6]]))
+        );
+        assert_eq!(
+            get_transposed_array(Alignment::Strict, &mut json!([])),
+            Ok(json!([]))
+        );
+        assert_eq!(
+            get_transposed_array(Alignment::Strict, &mut json!([[1, 2], [3]])),
+            Err(KjqlRunnerError::LengthMismatchError {
+                lengths: vec![2, 1]
+            })
+        );
+        assert_eq!(
+            get_transposed_array(Alignment::Pad, &mut json!([[1, 2], [3]])),
+            Ok(json!([[1, 3], [2, null]]))
+        );
+        assert_eq!(
+            get_transposed_array(Alignment::Pad, &mut json!([[1], 2])),
+            Err(KjqlRunnerError::InvalidArrayError(json!(2)))
+        );
+    }
+
+    #[test]
+    fn check_get_array_set_operations() {
+        let left = [json!(3), json!("a"), json!(1), json!(3), json!({ "b": 1 })];
+        let right = [json!(1.0), json!({ "b": 1 }), json!(2)];
+
+        assert_eq!(get_array_difference(&left, &right), json!([3, "a"]));
+        assert_eq!(
+            get_array_intersection(&left, &right),
+            json!([1, { "b": 1 }])
+        );
+        assert_eq!(
+            get_array_union(&left, &right),
+            json!([3, "a", 1, { "b": 1 }, 2])
+        );
+        assert_eq!(get_array_union(&[], &[]), json!([]));
+    }
+
+    #[test]
+    fn check_get_array_has_index() {
+        assert_eq!(
+            get_array_has_index(Index::new(1), &json!([1, null])),
+            json!(true)
+        );
+        assert_eq!(
+            get_array_has_index(Index::new(2), &json!([1, null])),
+            json!(false)
+        );
+        assert_eq!(
+            get_array_has_index(Index::new(0), &json!({ "0": 1 })),
+            json!(false)
+        );
+    }
+
+    #[test]
+    fn check_get_array_of_type() {
+        let value = json!([1, "a", null, [2], { "b": 3 }, true, 1.5]);
+
+        assert_eq!(
+            get_array_of_type(JsonType::Number, &mut value.clone()),
+            Ok(json!([1, 1.5]))
+        );
+        assert_eq!(
+            get_array_of_type(JsonType::Object, &mut value.clone()),
+            Ok(json!([{ "b": 3 }]))
+        );
+        assert_eq!(
+            get_array_of_type(JsonType::Boolean, &mut value.clone()),
+            Ok(json!([true]))
+        );
+        assert_eq!(
+            get_array_of_type(JsonType::String, &mut json!({ "a": 1 })),
+            Err(KjqlRunnerError::InvalidArrayError(json!({ "a": 1 })))
+        );
+    }
+
+    #[test]
+    fn check_get_sorted_array() {
+        let value = json!([3, "b", null, 1.5, true, "a", false, 2]);
+
+        assert_eq!(
+            get_sorted_array(Order::Ascending, &mut value.clone()),
+            Ok(json!([null, false, true, 1.5, 2, 3, "a", "b"]))
+        );
+        assert_eq!(
+            get_sorted_array(Order::Descending, &mut value.clone()),
+            Ok(json!(["b", "a", 3, 2, 1.5, true, false, null]))
+        );
+        assert_eq!(
+            get_sorted_array(Order::Ascending, &mut json!([])),
+            Ok(json!([]))
+        );
+
+        let value = json!({ "a": 1 });
+        assert_eq!(
+            get_sorted_array(Order::Ascending, &mut value.clone()),
+            Err(KjqlRunnerError::InvalidArrayError(value))
+        );
+    }
+
+    #[test]
+    fn check_get_sorted_array_by() {
+        let value = json!([
+            { "id": 1, "meta": { "created": 3 } },
+            { "id": 2, "meta": { "created": 1 } },
+            { "id": 3, "meta": { "created": 3 } },
+            { "id": 4, "meta": { "created": 2 } },
+        ]);
+        let tokens = [Token::KeySelector("meta"), Token::KeySelector("created")];
+
+        assert_eq!(
+            get_sorted_array_by(
+                &tokens,
+                Order::Ascending,
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
+            Ok(json!([
+                { "id": 2, "meta": { "created": 1 } },
+                { "id": 4, "meta": { "created": 2 } },
+                { "id": 1, "meta": { "created": 3 } },
+                { "id": 3, "meta": { "created": 3 } },
+            ]))
+        );
+        assert_eq!(
+            get_sorted_array_by(
+                &tokens,
+                Order::Descending,
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
+            Ok(json!([
+                { "id": 1, "meta": { "created": 3 } },
+                { "id": 3, "meta": { "created": 3 } },
+                { "id": 4, "meta": { "created": 2 } },
+                { "id": 2, "meta": { "created": 1 } },
+            ]))
+        );
+        assert_eq!(
+            get_sorted_array_by(
+                &[Token::KeySelector("nope")],
+                Order::Ascending,
+                &mut json!([{ "a": 1 }]),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "nope".to_string(),
+                parent: json!({ "a": 1 })
+            })
+        );
+    }
+
+    #[test]
+    fn check_get_unique_array() {
+        assert_eq!(
+            get_unique_array(&mut json!([3, "a", 1, 3, null, "a", 1.0, { "b": 1 }, { "b": 1 }])),
+            Ok(json!([3, "a", 1, null, { "b": 1 }]))
+        );
+        assert_eq!(get_unique_array(&mut json!([])), Ok(json!([])));
+
+        let value = json!("a");
+        assert_eq!(
+            get_unique_array(&mut value.clone()),
+            Err(KjqlRunnerError::InvalidArrayError(value))
+        );
+    }
+
+    #[test]
+    fn check_get_unique_array_by() {
+        let value = json!([
+            { "id": 1, "status": "open" },
+            { "id": 2, "status": "closed" },
+            { "id": 3, "status": "open" },
+            { "id": 4, "status": "draft" },
+        ]);
+
+        assert_eq!(
+            get_unique_array_by(
+                &[Token::KeySelector("status")],
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
+            Ok(json!([
+                { "id": 1, "status": "open" },
+                { "id": 2, "status": "closed" },
+                { "id": 4, "status": "draft" },
+            ]))
+        );
+        assert_eq!(
+            get_unique_array_by(
+                &[Token::KeySelector("nope")],
+                &mut json!([{ "a": 1 }]),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "nope".to_string(),
+                parent: json!({ "a": 1 })
+            })
+        );
+    }
+
+    #[test]
+    fn check_get_array_groups_by() {
+        let value = json!([
+            { "id": 1, "status": "open" },
+            { "id": 2, "status": "closed" },
+            { "id": 3, "status": "open" },
+            { "id": 4, "status": 1 },
+            { "id": 5, "status": null },
+        ]);
+
+        assert_eq!(
+            serde_json::to_string(
+                &get_array_groups_by(
+                    &[Token::KeySelector("status")],
+                    &mut value.clone(),
+                    RunnerOptions::default()
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            serde_json::to_string(&json!({
+                "open": [{ "id": 1, "status": "open" }, { "id": 3, "status": "open" }],
+                "closed": [{ "id": 2, "status": "closed" }],
+                "1": [{ "id": 4, "status": 1 }],
+                "null": [{ "id": 5, "status": null }],
+            }))
+            .unwrap()
+        );
+        assert_eq!(
+            get_array_groups_by(
+                &[Token::KeySelector("status")],
+                &mut json!([]),
+                RunnerOptions::default()
+            ),
+            Ok(json!({}))
+        );
+        assert_eq!(
+            get_array_groups_by(
+                &[Token::KeySelector("nope")],
+                &mut json!([{ "a": 1 }]),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "nope".to_string(),
+                parent: json!({ "a": 1 })
+            })
+        );
+    }
+
+    #[test]
+    fn check_get_array_sum() {
+        assert_eq!(get_array_sum(&mut json!([1, 2, 3])), Ok(json!(6)));
+        assert_eq!(get_array_sum(&mut json!([1, -2, 0.5])), Ok(json!(-0.5)));
+        assert_eq!(get_array_sum(&mut json!([])), Ok(json!(0)));
+        assert_eq!(
+            get_array_sum(&mut json!([1, "2", 3])),
+            Err(KjqlRunnerError::InvalidNumberError(json!("2")))
+        );
+        assert_eq!(
+            get_array_sum(&mut json!({ "a": 1 })),
+            Err(KjqlRunnerError::InvalidArrayError(json!({ "a": 1 })))
+        );
+
+        let large: Vec<u64> = (1..=10_000).collect();
+        assert_eq!(get_array_sum(&mut json!(large)), Ok(json!(50_005_000)));
+
+        // Overflowing partial sums don't turn the result into a float.
+        assert_eq!(
+            get_array_sum(&mut json!([u64::MAX, 1, -1])),
+            Ok(json!(u64::MAX))
+        );
+        assert_eq!(
+            get_array_sum(&mut json!([i64::MIN, -1, 1])),
+            Ok(json!(i64::MIN))
+        );
+        assert_eq!(
+            get_array_sum(&mut json!([u64::MAX, u64::MAX])),
+            Ok(json!(2.0 * u64::MAX as f64))
+        );
+
+        // Floats are added in the array order whatever its length.
+        let floats: Vec<f64> = (0..10_000).map(|index| 0.1 * f64::from(index)).collect();
+        assert_eq!(
+            get_array_sum(&mut json!(floats)),
+            Ok(json!(floats.iter().sum::<f64>()))
+        );
+        assert_eq!(
+            get_array_sum(&mut json!([0.1, 0.2, 0.3])),
+            Ok(json!(0.1 + 0.2 + 0.3))
+        );
+    }
+
+    #[test]
+    fn check_get_array_average() {
+        assert_eq!(get_array_average(&mut json!([1, 2, 3, 4])), Ok(json!(2.5)));
+        assert_eq!(get_array_average(&mut json!([2, 2])), Ok(json!(2.0)));
+        assert_eq!(
+            get_array_average(&mut json!([])),
+            Err(KjqlRunnerError::EmptyArrayError("Average".to_string()))
+        );
+        assert_eq!(
+            get_array_average(&mut json!([1, null])),
+            Err(KjqlRunnerError::InvalidNumberError(json!(null)))
+        );
+    }
+
+    #[test]
+    fn check_get_array_min_max() {
+        let value = json!([3, -1.5, 7, 2, 7]);
+
+        assert_eq!(get_array_min(&mut value.clone()), Ok(json!(-1.5)));
+        assert_eq!(get_array_max(&mut value.clone()), Ok(json!(7)));
+        assert_eq!(
+            get_array_min(&mut json!([])),
+            Err(KjqlRunnerError::EmptyArrayError("Min".to_string()))
+        );
+        assert_eq!(
+            get_array_max(&mut json!([])),
+            Err(KjqlRunnerError::EmptyArrayError("Max".to_string()))
+        );
+        assert_eq!(
+            get_array_max(&mut json!([1, true])),
+            Err(KjqlRunnerError::InvalidNumberError(json!(true)))
+        );
+    }
+
+    #[test]
+    fn check_get_array_min_max_by() {
+        let value = json!([
+            { "id": 1, "cpu": 30 },
+            { "id": 2, "cpu": 10 },
+            { "id": 3, "cpu": 90 },
+            { "id": 4, "cpu": 10 },
+            { "id": 5, "cpu": 90 },
+        ]);
+        let tokens = [Token::KeySelector("cpu")];
+
+        assert_eq!(
+            get_array_min_by(&tokens, &mut value.clone(), RunnerOptions::default()),
+            Ok(json!({ "id": 2, "cpu": 10 }))
+        );
+        assert_eq!(
+            get_array_max_by(&tokens, &mut value.clone(), RunnerOptions::default()),
+            Ok(json!({ "id": 3, "cpu": 90 }))
+        );
+        assert_eq!(
+            get_array_min_by(&tokens, &mut json!([]), RunnerOptions::default()),
+            Err(KjqlRunnerError::EmptyArrayError("Min by".to_string()))
+        );
+        assert_eq!(
+            get_array_max_by(
+                &tokens,
+                &mut json!([{ "cpu": "high" }]),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidNumberError(json!("high")))
+        );
     }
 }
@@ -2,6 +2,8 @@ use kjql_parser::errors::KjqlParserError;
 use serde_json::Value;
 use thiserror::Error;
 
+use crate::value::get_json_type;
+
 static SLICE_SEP: &str = " ... ";
 static SLICE_LEN: usize = 7;
 static SEP: &str = ", ";
@@ -23,18 +25,6 @@ fn shorten(json: &Value) -> String {
     [start_slice, end_slice].join(SLICE_SEP)
 }
 
-/// Returns the type of JSON `Value`.
-fn get_json_type(json: &Value) -> &str {
-    match json {
-        Value::Array(_) => "array",
-        Value::Bool(_) => "boolean",
-        Value::Null => "null",
-        Value::Number(_) => "number",
-        Value::Object(_) => "object",
-        Value::String(_) => "string",
-    }
-}
-
 /// Error type returned by the runner.
 #[derive(Debug, Error, PartialEq)]
 pub enum KjqlRunnerError {
@@ -42,10 +32,27 @@ pub enum KjqlRunnerError {
     #[error("Query is empty")]
     EmptyQueryError,
 
+    /// Division by zero error.
+    #[error("Division of {0} by zero")]
+    DivisionByZeroError(Value),
+
+    /// Empty array error.
+    #[error("{0} operator used on an empty array")]
+    EmptyArrayError(String),
+
     /// Flatten error.
     #[error("Value {0} is neither an array nor an object and cna't be flattened")]
     FlattenError(Value),
 
+    /// From JSON error.
+    #[error("Value {svalue} can't be parsed as JSON: {reason}", svalue = shorten(value))]
+    FromJsonError {
+        /// Value.
+        value: Value,
+        /// Reason of the failure.
+        reason: String,
+    },
+
     /// Index out of bounds error.
     #[error("Index {index} in parent {parent} is out of bounds")]
     IndexOutOfBoundsError {
@@ -55,14 +62,53 @@ pub enum KjqlRunnerError {
         parent: Value,
     },
 
+    /// Index counted from the end out of bounds error.
+    #[error("Index -{index} in parent {parent} is out of bounds")]
+    IndexFromEndOutOfBoundsError {
+        /// Index counted from the end.
+        index: usize,
+        /// Parent value.
+        parent: Value,
+    },
+
     /// Invalid array error.
     #[error("Value {0} is not a JSON array ({type})", type = get_json_type(.0))]
     InvalidArrayError(Value),
 
+    /// Invalid operands error.
+    #[error(
+        "Operator {operator} can't be applied to {sleft} ({left_type}) and {sright} ({right_type})",
+        sleft = shorten(&operands.0),
+        left_type = get_json_type(&operands.0),
+        sright = shorten(&operands.1),
+        right_type = get_json_type(&operands.1)
+    )]
+    InvalidOperandsError {
+        /// Operator.
+        operator: String,
+        /// Left and right operands.
+        operands: Box<(Value, Value)>,
+    },
+
+    /// Invalid number error.
+    #[error("Value {0} is not a JSON number ({type})", type = get_json_type(.0))]
+    InvalidNumberError(Value),
+
     /// Invalid object error.
     #[error("Value {0} is not a JSON object ({type})", type = get_json_type(.0))]
     InvalidObjectError(Value),
 
+    /// Invalid string error.
+    #[error("Value {0} is not a JSON string ({type})", type = get_json_type(.0))]
+    InvalidStringError(Value),
+
+    /// Length mismatch error.
+    #[error("Arrays of lengths {join_lengths} can't be aligned", join_lengths = join(&lengths.iter().map(ToString::to_string).collect::<Vec<String>>()))]
+    LengthMismatchError {
+        /// Lengths of the arrays.
+        lengths: Vec<usize>,
+    },
+
     /// Key not found error.
     #[error(r#"Key "{key}" doesn't exist in parent {sparent}"#, sparent = shorten(parent))]
     KeyNotFoundError {
@@ -82,6 +128,14 @@ pub enum KjqlRunnerError {
         parent: Value,
     },
 
+    /// Number conversion error.
+    #[error("Value {svalue} can't be converted to a JSON number", svalue = shorten(.0))]
+    NumberConversionError(Value),
+
+    /// Number overflow error.
+    #[error("Number overflow, the result is not a finite JSON number")]
+    NumberOverflowError,
+
     /// Parsing error.
     #[error(transparent)]
     ParsingError(#[from] KjqlParserError),
@@ -108,6 +162,10 @@ pub enum KjqlRunnerError {
     /// Unknown error.
     #[error("Unknown error")]
     UnknownError,
+
+    /// Unsupported pointer error.
+    #[error("Token {0} builds a new value which has no JSON Pointer")]
+    UnsupportedPointerError(String),
 }
 
 #[cfg(test)]
@@ -122,12 +180,12 @@ mod tests {
 
     #[test]
     fn check_get_json_type() {
-        assert_eq!("array", get_json_type(&json!([])));
-        assert_eq!(get_json_type(&json!(true)), "boolean");
-        assert_eq!(get_json_type(&json!(null)), "null");
-        assert_eq!(get_json_type(&json!(1)), "number");
-        assert_eq!(get_json_type(&json!({})), "object");
-        assert_eq!(get_json_type(&json!("a")), "string");
+        assert_eq!("array", get_json_type(&json!([])).to_string());
+        assert_eq!(get_json_type(&json!(true)).to_string(), "boolean");
+        assert_eq!(get_json_type(&json!(null)).to_string(), "null");
+        assert_eq!(get_json_type(&json!(1)).to_string(), "number");
+        assert_eq!(get_json_type(&json!({})).to_string(), "object");
+        assert_eq!(get_json_type(&json!("a")).to_string(), "string");
     }
 
     #[test]
@@ -0,0 +1,267 @@
+use kjql_parser::tokens::{
+    BinaryOperator,
+    Expression,
+    Lens,
+    Token,
+};
+use serde_json::Value;
+
+use crate::{
+    array::{
+        get_array_difference,
+        get_array_intersection,
+        get_array_union,
+        is_matching_lenses,
+    },
+    errors::KjqlRunnerError,
+    object::{
+        get_object_difference,
+        get_object_intersection,
+        get_object_union,
+    },
+    runner::{
+        RunnerOptions,
+        group_runner_with_options,
+        token_with_options,
+    },
+    value::{
+        as_number,
+        compute_numbers,
+    },
+};
+
+/// Takes a `BinaryOperator` and two references of JSON `Value`.
+/// Returns the result of the operation as a JSON `Value` or an error.
+/// Arrays and objects support the set operators, numbers the arithmetic
+/// ones. The subtraction is shared as the difference.
+fn apply_binary_operator(
+    operator: BinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Result<Value, KjqlRunnerError> {
+    match (operator, left, right) {
+        (BinaryOperator::Subtract, Value::Array(left), Value::Array(right)) => {
+            Ok(get_array_difference(left, right))
+        }
+        (BinaryOperator::Intersection, Value::Array(left), Value::Array(right)) => {
+            Ok(get_array_intersection(left, right))
+        }
+        (BinaryOperator::Union, Value::Array(left), Value::Array(right)) => {
+            Ok(get_array_union(left, right))
+        }
+        (BinaryOperator::Subtract, Value::Object(left), Value::Object(right)) => {
+            Ok(get_object_difference(left, right))
+        }
+        (BinaryOperator::Intersection, Value::Object(left), Value::Object(right)) => {
+            Ok(get_object_intersection(left, right))
+        }
+        (BinaryOperator::Union, Value::Object(left), Value::Object(right)) => {
+            Ok(get_object_union(left, right))
+        }
+        (BinaryOperator::Intersection | BinaryOperator::Union, _, _) => {
+            Err(KjqlRunnerError::InvalidOperandsError {
+                operator: operator.to_string(),
+                operands: Box::new((left.clone(), right.clone())),
+            })
+        }
+        _ => compute_numbers(operator, as_number(left)?, as_number(right)?).map(Value::Number),
+    }
+}
+
+/// Takes a reference of an `Expression`, a reference of a JSON `Value` and
+/// the `RunnerOptions`.
+/// Returns the evaluated expression as a JSON `Value` or an error.
+/// Both sides of a binary operation are evaluated in parallel.
+pub(crate) fn get_expression(
+    expression: &Expression,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    match expression {
+        Expression::Binary(left, operator, right) => {
+            let (left, right) = rayon::join(
+                || get_expression(left, json, options),
+                || get_expression(right, json, options),
+            );
+
+            apply_binary_operator(*operator, &left?, &right?)
+        }
+        Expression::Operand(tokens) => {
+            let tokens: Vec<&Token> = tokens.iter().collect();
+
+            group_runner_with_options(&tokens, json, options)
+        }
+    }
+}
+
+/// Takes a slice of `Lens`, the tokens of both branches, a reference of a
+/// JSON `Value` and the `RunnerOptions`.
+/// Returns the consequent if any of the lenses matches, the alternative
+/// otherwise, as a JSON `Value` or an error.
+pub(crate) fn get_conditional(
+    lenses: &[Lens],
+    consequent: &[Token],
+    alternative: &[Token],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    if is_matching_lenses(lenses, json, options) {
+        token_with_options(consequent, json, options)
+    } else {
+        token_with_options(alternative, json, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kjql_parser::tokens::{
+        BinaryOperator,
+        Expression,
+        Lens,
+        LensValue,
+        Number,
+        Token,
+    };
+    use serde_json::json;
+
+    use super::{
+        get_conditional,
+        get_expression,
+    };
+    use crate::{
+        errors::KjqlRunnerError,
+        runner::RunnerOptions,
+    };
+
+    #[test]
+    fn check_get_expression() {
+        let value = json!({ "price": 2.5, "qty": 4, "name": "a" });
+        let operand = |key| Expression::Operand(vec![Token::KeySelector(key)]);
+
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("price"), BinaryOperator::Multiply, operand("qty")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!(10.0))
+        );
+        assert_eq!(
+            get_expression(
+                &Expression::binary(
+                    operand("qty"),
+                    BinaryOperator::Subtract,
+                    Expression::Operand(vec![Token::NumberLiteral(Number::Integer(5))])
+                ),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!(-1))
+        );
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("name"), BinaryOperator::Add, operand("qty")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidNumberError(json!("a")))
+        );
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("price"), BinaryOperator::Add, operand("nope")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "nope".to_string(),
+                parent: value.clone()
+            })
+        );
+    }
+
+    #[test]
+    fn check_get_set_expression() {
+        let value = json!({ "a": [1, 2, 3], "b": [3, 4], "c": { "x": 1 }, "d": 1 });
+        let operand = |key| Expression::Operand(vec![Token::KeySelector(key)]);
+
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("a"), BinaryOperator::Subtract, operand("b")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!([1, 2]))
+        );
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("a"), BinaryOperator::Union, operand("b")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!([1, 2, 3, 4]))
+        );
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("a"), BinaryOperator::Intersection, operand("c")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidOperandsError {
+                operator: "&".to_string(),
+                operands: Box::new((json!([1, 2, 3]), json!({ "x": 1 }))),
+            })
+        );
+        assert_eq!(
+            get_expression(
+                &Expression::binary(operand("a"), BinaryOperator::Subtract, operand("d")),
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidNumberError(json!([1, 2, 3])))
+        );
+    }
+
+    #[test]
+    fn check_get_conditional() {
+        let lenses = [Lens::new(
+            &[Token::KeySelector("level")],
+            Some(LensValue::String("high")),
+        )];
+        let consequent = [Token::KeySelector("a")];
+        let alternative = [Token::KeySelector("b")];
+
+        assert_eq!(
+            get_conditional(
+                &lenses,
+                &consequent,
+                &alternative,
+                &json!({ "level": "high", "a": 1, "b": 2 }),
+                RunnerOptions::default()
+            ),
+            Ok(json!(1))
+        );
+        assert_eq!(
+            get_conditional(
+                &lenses,
+                &consequent,
+                &alternative,
+                &json!({ "level": "low", "a": 1, "b": 2 }),
+                RunnerOptions::default()
+            ),
+            Ok(json!(2))
+        );
+        assert_eq!(
+            get_conditional(
+                &lenses,
+                &consequent,
+                &alternative,
+                &json!({ "a": 1 }),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "b".to_string(),
+                parent: json!({ "a": 1 })
+            })
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use kjql_parser::{
+    pointer::escape_reference,
+    tokens::{
+        PathSegment,
+        PathSelector,
+    },
+};
+use serde_json::{
+    Value,
+    json,
+};
+
+use crate::{
+    array::is_matching_lenses,
+    runner::RunnerOptions,
+};
+
+/// Node of a nodelist as its JSON Pointer and a reference of its JSON `Value`.
+pub(crate) type Node<'a> = (String, &'a Value);
+
+/// Takes the children of a node as tuples of reference tokens and values.
+/// Returns them as nodes located under the node.
+fn to_nodes<'a>(
+    pointer: &str,
+    children: impl Iterator<Item = (String, &'a Value)>,
+) -> Vec<Node<'a>> {
+    children
+        .map(|(reference, value)| (format!("{pointer}/{reference}"), value))
+        .collect()
+}
+
+/// Takes a reference of a JSON `Value` and returns its children as tuples of
+/// escaped reference tokens and values.
+fn get_children(json: &Value) -> Vec<(String, &Value)> {
+    match json {
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (index.to_string(), value))
+            .collect(),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| (escape_reference(key), value))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Takes a `PathSelector`, a node and a mutable reference of the selected
+/// nodes.
+/// Pushes the children of the node matched by the selector, the missing ones
+/// and the ones of a value of another type being skipped.
+fn push_selected<'a>(selector: &PathSelector, (pointer, json): &Node<'a>, acc: &mut Vec<Node<'a>>) {
+    match (selector, json) {
+        (PathSelector::Keys(keys), Value::Object(object)) => acc.extend(to_nodes(
+            pointer,
+            keys.iter().filter_map(|key| {
+                object
+                    .get_key_value(*key)
+                    .map(|(key, value)| (escape_reference(key), value))
+            }),
+        )),
+        (PathSelector::Indexes(indexes), Value::Array(array)) => acc.extend(to_nodes(
+            pointer,
+            indexes.iter().filter_map(|index| {
+                let index: usize = (*index).into();
+
+                array.get(index).map(|value| (index.to_string(), value))
+            }),
+        )),
+        (PathSelector::Slice(start, end), Value::Array(array)) => {
+            let end = end.map_or(array.len(), usize::from).min(array.len());
+            let start = usize::from(*start).min(end);
+
+            acc.extend(to_nodes(
+                pointer,
+                array[start..end]
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| ((start + index).to_string(), value)),
+            ));
+        }
+        // Filters are evaluated strictly, a missing path never matches.
+        (PathSelector::Filter(lenses), _) => acc.extend(to_nodes(
+            pointer,
+            get_children(json)
+                .into_iter()
+                .filter(|(_, value)| is_matching_lenses(lenses, value, RunnerOptions::default())),
+        )),
+        (PathSelector::Wildcard, _) => {
+            acc.extend(to_nodes(pointer, get_children(json).into_iter()))
+        }
+        _ => {}
+    }
+}
+
+/// Takes a node and a mutable reference of the nodes.
+/// Pushes the node followed by all its descendants, in document order.
+fn push_descendants<'a>(node: Node<'a>, acc: &mut Vec<Node<'a>>) {
+    let children = to_nodes(&node.0, get_children(node.1).into_iter());
+
+    acc.push(node);
+    children
+        .into_iter()
+        .for_each(|child| push_descendants(child, acc));
+}
+
+/// Takes a slice of `PathSegment` and a node.
+/// Returns the nodelist selected by the segments from the node.
+pub(crate) fn get_nodes<'a>(segments: &[PathSegment], node: Node<'a>) -> Vec<Node<'a>> {
+    segments.iter().fold(vec![node], |nodes, segment| {
+        let (descendant, selector) = segment.get();
+        let nodes = if descendant {
+            nodes.into_iter().fold(vec![], |mut acc, node| {
+                push_descendants(node, &mut acc);
+                acc
+            })
+        } else {
+            nodes
+        };
+
+        nodes.iter().fold(vec![], |mut acc, node| {
+            push_selected(selector, node, &mut acc);
+            acc
+        })
+    })
+}
+
+/// Returns `true` if the segments select at most one node.
+pub(crate) fn is_singular(segments: &[PathSegment]) -> bool {
+    segments.iter().all(|segment| {
+        let (descendant, selector) = segment.get();
+
+        !descendant && selector.is_singular()
+    })
+}
+
+/// Takes a slice of `PathSegment` and a reference of a JSON `Value`.
+/// Returns the selected nodelist as a JSON `Value` array, or the selected node
+/// for a singular path, `null` if it is missing.
+pub(crate) fn get_jsonpath(segments: &[PathSegment], json: &Value) -> Value {
+    let nodes = get_nodes(segments, (String::new(), json));
+
+    if is_singular(segments) {
+        return nodes
+            .first()
+            .map_or(Value::Null, |(_, value)| (*value).clone());
+    }
+
+    json!(
+        nodes
+            .into_iter()
+            .map(|(_, value)| value.clone())
+            .collect::<Vec<Value>>()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use kjql_parser::{
+        jsonpath::parse_jsonpath,
+        tokens::Token,
+    };
+    use serde_json::{
+        Value,
+        json,
+    };
+
+    use super::{
+        get_jsonpath,
+        get_nodes,
+    };
+
+    fn jsonpath(path: &str, json: &Value) -> Value {
+        match parse_jsonpath(path).unwrap().as_slice() {
+            [Token::JsonPathSelector(segments)] => get_jsonpath(segments, json),
+            _ => unreachable!(),
+        }
+    }
+
+    fn pointers(path: &str, json: &Value) -> Vec<String> {
+        match parse_jsonpath(path).unwrap().as_slice() {
+            [Token::JsonPathSelector(segments)] => get_nodes(segments, (String::new(), json))
+                .into_iter()
+                .map(|(pointer, _)| pointer)
+                .collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn check_get_jsonpath_missing_nodes() {
+        let value = json!({ "n": [1, 2], "b": { "c": 1, "d": 2 }, "s": "str" });
+
+        assert_eq!(jsonpath("$.n[0,7]", &value), json!([1]));
+        assert_eq!(jsonpath("$.b['c','zz']", &value), json!([1]));
+        assert_eq!(jsonpath("$.s[0:2]", &value), json!([]));
+        assert_eq!(jsonpath("$.b[1:2]", &value), json!([]));
+        assert_eq!(jsonpath("$.s[*]", &value), json!([]));
+        assert_eq!(jsonpath("$.n[5]", &value), json!(null));
+        assert_eq!(jsonpath("$.zz.a", &value), json!(null));
+        assert_eq!(jsonpath("$.zz[*]", &value), json!([]));
+    }
+
+    #[test]
+    fn check_get_jsonpath_selectors() {
+        let value = json!({
+            "a": [1, 2, 3],
+            "store": { "book": [{ "isbn": "1", "title": "x" }, { "title": "y" }] }
+        });
+
+        assert_eq!(jsonpath("$", &value), value);
+        assert_eq!(jsonpath("$.a[1:10]", &value), json!([2, 3]));
+        assert_eq!(jsonpath("$.a[2:1]", &value), json!([]));
+        assert_eq!(jsonpath("$.a[1]", &value), json!(2));
+        assert_eq!(jsonpath("$.store.book[*].isbn", &value), json!(["1"]));
+        assert_eq!(
+            jsonpath("$..book[?(@.isbn)]", &value),
+            json!([{ "isbn": "1", "title": "x" }])
+        );
+        assert_eq!(jsonpath("$..book[?(@.isbn)].title", &value), json!(["x"]));
+        assert_eq!(jsonpath("$..title", &value), json!(["x", "y"]));
+        assert_eq!(jsonpath("$.*[1:]", &value), json!([2, 3]));
+        assert_eq!(
+            jsonpath("$..[0]", &value),
+            json!([1, { "isbn": "1", "title": "x" }])
+        );
+    }
+
+    #[test]
+    fn check_get_nodes_pointers() {
+        let value = json!({ "a/b": [{ "c": 1 }, { "c": 2 }] });
+
+        assert_eq!(
+            pointers("$['a/b'][*].c", &value),
+            ["/a~1b/0/c", "/a~1b/1/c"]
+        );
+        assert_eq!(pointers("$['a/b'][1:]", &value), ["/a~1b/1"]);
+        assert_eq!(pointers("$.zz", &value), Vec::<String>::new());
+    }
+}
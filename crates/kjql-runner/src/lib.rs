@@ -3,6 +3,14 @@
 #![warn(missing_debug_implementations, missing_docs, unreachable_pub)]
 #![doc = include_str!("../README.md")]
 mod array;
+/// Runner errors.
 pub mod errors;
+mod expression;
+mod jsonpath;
 mod object;
+/// JSON Pointers of the results.
+pub mod pointer;
+/// Runner.
 pub mod runner;
+mod string;
+mod value;
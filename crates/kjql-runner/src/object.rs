@@ -1,7 +1,4 @@
-use std::{
-    num::NonZeroUsize,
-    string::ToString,
-};
+use std::string::ToString;
 
 use indexmap::{
     IndexMap,
@@ -9,7 +6,11 @@ use indexmap::{
 };
 use kjql_parser::tokens::{
     Index,
+    KeyRange,
+    MergeStrategy,
     Range,
+    Selection,
+    Token,
 };
 use rayon::prelude::*;
 use serde_json::{
@@ -18,7 +19,14 @@ use serde_json::{
     json,
 };
 
-use crate::errors::KjqlRunnerError;
+use crate::{
+    errors::KjqlRunnerError,
+    runner::{
+        RunnerOptions,
+        group_runner_with_options,
+    },
+    value::get_range_boundaries,
+};
 
 /// Takes a reference of a JSON `Value` and returns a reference of a JSON `Map` or an error.
 fn as_object_mut(json: &mut Value) -> Result<&mut Map<String, Value>, KjqlRunnerError> {
@@ -45,6 +53,117 @@ pub(crate) fn get_object_key(key: &str, json: &Value) -> Result<Value, KjqlRunne
         .cloned()
 }
 
+/// Takes a key as a string slice and a reference of a JSON `Value`.
+/// Returns `true` as a JSON `Value` if the value is an object containing the
+/// key, `false` otherwise.
+pub(crate) fn get_object_has_key(key: &str, json: &Value) -> Value {
+    json!(
+        json.as_object()
+            .is_some_and(|object| object.contains_key(key))
+    )
+}
+
+/// Takes two references of JSON `Map`.
+/// Returns the entries of the left object whose keys are missing from the
+/// right one as a JSON `Value`.
+pub(crate) fn get_object_difference(
+    left: &Map<String, Value>,
+    right: &Map<String, Value>,
+) -> Value {
+    Value::Object(
+        left.iter()
+            .filter(|(key, _)| !right.contains_key(*key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    )
+}
+
+/// Takes two references of JSON `Map`.
+/// Returns the entries of the right object whose keys are found in the left
+/// one, in the left order, as a JSON `Value`.
+pub(crate) fn get_object_intersection(
+    left: &Map<String, Value>,
+    right: &Map<String, Value>,
+) -> Value {
+    Value::Object(
+        left.keys()
+            .filter_map(|key| right.get(key).map(|value| (key.clone(), value.clone())))
+            .collect(),
+    )
+}
+
+/// Takes two references of JSON `Map`.
+/// Returns the entries of both objects as a JSON `Value`, the right values
+/// taking precedence over the left ones.
+pub(crate) fn get_object_union(left: &Map<String, Value>, right: &Map<String, Value>) -> Value {
+    let mut result = left.clone();
+    result.extend(
+        right
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+
+    Value::Object(result)
+}
+
+/// Deep merges the right JSON `Map` into the left one.
+/// Nested objects are merged, arrays are concatenated or replaced depending on
+/// the strategy and any other value is replaced.
+fn merge_objects(
+    left: &mut Map<String, Value>,
+    right: Map<String, Value>,
+    strategy: MergeStrategy,
+) {
+    for (key, right_value) in right {
+        match (left.get_mut(&key), right_value) {
+            (Some(Value::Object(left_object)), Value::Object(right_object)) => {
+                merge_objects(left_object, right_object, strategy);
+            }
+            (Some(Value::Array(left_array)), Value::Array(right_array))
+                if strategy == MergeStrategy::Concatenate =>
+            {
+                left_array.extend(right_array);
+            }
+            (_, right_value) => {
+                left.insert(key, right_value);
+            }
+        }
+    }
+}
+
+/// Takes a slice of sub-queries, a merge strategy and a reference of a JSON
+/// `Value`.
+/// Returns the deep merged objects of the sub-queries as a JSON `Value` or an
+/// error, the right objects taking precedence over the left ones.
+pub(crate) fn get_merged_objects(
+    sub_queries: &[Vec<Token>],
+    strategy: MergeStrategy,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let objects = sub_queries
+        .par_iter()
+        .map(|tokens| {
+            let tokens: Vec<&Token> = tokens.iter().collect();
+
+            match group_runner_with_options(&tokens, json, options)? {
+                Value::Object(object) => Ok(object),
+                value => Err(KjqlRunnerError::InvalidObjectError(value)),
+            }
+        })
+        .collect::<Result<Vec<Map<String, Value>>, KjqlRunnerError>>()?;
+
+    let result = objects
+        .into_iter()
+        .reduce(|mut acc, object| {
+            merge_objects(&mut acc, object, strategy);
+            acc
+        })
+        .unwrap_or_default();
+
+    Ok(Value::Object(result))
+}
+
 /// Takes a key as a string slice and a reference of a JSON `Value`.
 /// Returns a JSON `Value` or an error.
 pub(crate) fn get_object_multi_key(
@@ -144,11 +263,14 @@ fn flatten_object(
     }
 }
 
-/// Takes a slice of `Index` and a mutable reference of a JSON `Value`.
+/// Takes a slice of `Index`, a mutable reference of a JSON `Value` and the
+/// `RunnerOptions`.
 /// Returns a reference of a JSON `Value` or an error.
+/// In lenient mode, missing indexes are skipped.
 pub(crate) fn get_object_indexes(
     indexes: &[Index],
     json: &mut Value,
+    options: RunnerOptions,
 ) -> Result<Value, KjqlRunnerError> {
     let mut_object = as_object_mut(json)?;
 
@@ -158,7 +280,7 @@ pub(crate) fn get_object_indexes(
 
     let len = indexes.len();
     let max: usize = (*indexes.iter().max().unwrap()).into();
-    if max + 1 > mut_object.len() {
+    if !options.lenient && max + 1 > mut_object.len() {
         return Err(KjqlRunnerError::IndexOutOfBoundsError {
             index: max,
             parent: json.clone(),
@@ -203,28 +325,20 @@ pub(crate) fn get_object_indexes(
     Ok(json!(new_map))
 }
 
-/// Takes a reference of a `Range` and a mutable reference of a JSON `Value`.
+/// Takes a reference of a `Range`, a mutable reference of a JSON `Value` and
+/// the `RunnerOptions`.
 /// Returns a reference of a JSON `Value` or an error.
-pub(crate) fn get_object_range(range: &Range, json: &mut Value) -> Result<Value, KjqlRunnerError> {
-    let mut_object = as_object_mut(json)?;
-
-    if mut_object.is_empty() {
+/// In lenient mode, the boundaries are clamped to the length of the object.
+pub(crate) fn get_object_range(
+    range: &Range,
+    json: &mut Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let len = as_object_mut(json)?.len();
+    let Some((start, end)) = get_range_boundaries(range, json, len, options)? else {
         return Ok(json!({}));
-    }
-
-    let len = mut_object.len();
-    // Object's length can't be zero so we can safely unwrap here.
-    let non_zero_len = NonZeroUsize::new(len).unwrap();
-    let (start, end) = range.to_boundaries(non_zero_len);
-
-    // Out of bounds.
-    if start + 1 > len || end + 1 > len {
-        return Err(KjqlRunnerError::RangeOutOfBoundsError {
-            start,
-            end,
-            parent: json.clone(),
-        });
-    }
+    };
+    let mut_object = as_object_mut(json)?;
 
     let is_natural_order = start < end;
     let mut result = mut_object
@@ -272,6 +386,134 @@ pub(crate) fn get_object_range(range: &Range, json: &mut Value) -> Result<Value,
     Ok(json!(new_map))
 }
 
+/// Takes a slice of `Selection`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns the selected entries concatenated in the order of the selections
+/// as a JSON `Value` or an error.
+/// An entry selected more than once keeps its first position.
+pub(crate) fn get_object_selections(
+    selections: &[Selection],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let Some(object) = json.as_object() else {
+        return Err(KjqlRunnerError::InvalidObjectError(json.clone()));
+    };
+    let len = object.len();
+    let get_entry = |index: usize| {
+        object
+            .iter()
+            .nth(index)
+            .map(|(key, value)| (key.clone(), value.clone()))
+    };
+
+    let result = selections
+        .iter()
+        .try_fold(Map::new(), |mut acc, selection| {
+            match selection {
+                Selection::Index(index) => match get_entry((*index).into()) {
+                    Some((key, value)) => {
+                        acc.entry(key).or_insert(value);
+                    }
+                    // Indexes of an empty object select nothing.
+                    None if options.lenient || object.is_empty() => {}
+                    None => {
+                        return Err(KjqlRunnerError::IndexOutOfBoundsError {
+                            index: (*index).into(),
+                            parent: json.clone(),
+                        });
+                    }
+                },
+                Selection::IndexFromEnd(index) => match index.from_end(len) {
+                    Some(index) => {
+                        if let Some((key, value)) = get_entry(index.into()) {
+                            acc.entry(key).or_insert(value);
+                        }
+                    }
+                    None if options.lenient => {}
+                    None => {
+                        return Err(KjqlRunnerError::IndexFromEndOutOfBoundsError {
+                            index: (*index).into(),
+                            parent: json.clone(),
+                        });
+                    }
+                },
+                Selection::Range(range) => {
+                    if let Some((start, end)) = get_range_boundaries(range, json, len, options)? {
+                        let entries = object
+                            .iter()
+                            .skip(start.min(end))
+                            .take(start.abs_diff(end) + 1);
+                        let insert = |(key, value): (&String, &Value)| {
+                            acc.entry(key.clone()).or_insert_with(|| value.clone());
+                        };
+
+                        if start < end {
+                            entries.for_each(insert);
+                        } else {
+                            entries.rev().for_each(insert);
+                        }
+                    }
+                }
+            }
+
+            Ok::<Map<String, Value>, KjqlRunnerError>(acc)
+        })?;
+
+    Ok(Value::Object(result))
+}
+
+/// Takes a reference of a `KeyRange` and a reference of a JSON `Value`.
+/// Returns the entries from the start key to the end key in document order as
+/// a JSON `Value` or an error.
+/// The entries are reversed if the end key comes before the start key.
+pub(crate) fn get_object_key_range(
+    range: &KeyRange,
+    json: &Value,
+) -> Result<Value, KjqlRunnerError> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| KjqlRunnerError::InvalidObjectError(json.clone()))?;
+    let (start_key, end_key, inclusive) = range.get();
+
+    let position = |key: &str| {
+        object
+            .keys()
+            .position(|k| k == key)
+            .ok_or_else(|| KjqlRunnerError::KeyNotFoundError {
+                key: key.to_string(),
+                parent: json.clone(),
+            })
+    };
+    let start = position(start_key)?;
+    let end = position(end_key)?;
+
+    let is_natural_order = start <= end;
+    let (lower, upper) = if is_natural_order {
+        (start, end)
+    } else {
+        (end, start)
+    };
+    let mut entries: Vec<(&String, &Value)> =
+        object.iter().skip(lower).take(upper - lower + 1).collect();
+
+    if !is_natural_order {
+        entries.reverse();
+    }
+
+    // The end key is always the last entry at this point.
+    if !inclusive {
+        entries.pop();
+    }
+
+    Ok(Value::Object(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    ))
+}
+
 /// Takes a mutalbe reference of a JSON `Value`.
 /// Converts the original object as an array of its keys and returns a JSON `Value` or an error.
 /// Note: the runner checks that the input is a JSON object.
@@ -284,24 +526,35 @@ pub(crate) fn get_object_as_keys(json: &mut Value) -> Result<Value, KjqlRunnerEr
             acc.push(json!(k));
             Ok::<Vec<Value>, KjqlRunnerError>(acc)
         })
-        .try_reduce(
-            || Vec::new(),
-            |mut a, mut b| {
-                a.append(&mut b);
-                Ok(a)
-            },
-        )?;
+        .try_reduce(Vec::new, |mut a, mut b| {
+            a.append(&mut b);
+            Ok(a)
+        })?;
     // Restore the original order.
     // We can safely unwrap here since the key is a string.
     result.par_sort_by_key(|v| String::from(v.as_str().unwrap()));
     Ok(json!(result))
 }
 
+/// Takes a reference of a JSON `Value`.
+/// Returns the values of an object as a JSON `Value` array, in the order of
+/// their keys, or an error.
+pub(crate) fn get_object_values(json: &Value) -> Result<Value, KjqlRunnerError> {
+    match json {
+        Value::Object(object) => Ok(json!(object.values().cloned().collect::<Vec<Value>>())),
+        _ => Err(KjqlRunnerError::InvalidObjectError(json.clone())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use kjql_parser::tokens::{
         Index,
+        KeyRange,
+        MergeStrategy,
         Range,
+        Selection,
+        Token,
     };
     use serde_json::{
         Value,
@@ -310,13 +563,24 @@ mod tests {
 
     use super::{
         get_flattened_object,
+        get_merged_objects,
         get_object_as_keys,
+        get_object_difference,
+        get_object_has_key,
         get_object_indexes,
+        get_object_intersection,
         get_object_key,
+        get_object_key_range,
         get_object_multi_key,
         get_object_range,
+        get_object_selections,
+        get_object_union,
+        get_object_values,
+    };
+    use crate::{
+        errors::KjqlRunnerError,
+        runner::RunnerOptions,
     };
-    use crate::errors::KjqlRunnerError;
 
     /// If we perform a direct comparison between the processed value and
     /// the expected value from the `json!` macro, we might get a false
@@ -345,6 +609,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_get_merged_objects() {
+        let value = json!({
+            "defaults": { "db": { "host": "localhost", "port": 5432 }, "tags": ["a"], "debug": false },
+            "env": { "db": { "host": "db.prod" }, "tags": ["b"] },
+            "local": { "debug": true, "tags": null },
+            "list": [],
+        });
+        let sub_queries = |keys: &[&'static str]| {
+            keys.iter()
+                .map(|key| vec![Token::KeySelector(key)])
+                .collect::<Vec<Vec<Token>>>()
+        };
+
+        assert_eq!(
+            get_merged_objects(
+                &sub_queries(&["defaults", "env"]),
+                MergeStrategy::Replace,
+                &value,
+                RunnerOptions::default()
+            )
+            .map(|value| value.to_string()),
+            Ok(r#"{"db":{"host":"db.prod","port":5432},"tags":["b"],"debug":false}"#.to_string())
+        );
+        assert_eq!(
+            get_merged_objects(
+                &sub_queries(&["defaults", "env"]),
+                MergeStrategy::Concatenate,
+                &value,
+                RunnerOptions::default()
+            )
+            .map(|value| value.to_string()),
+            Ok(
+                r#"{"db":{"host":"db.prod","port":5432},"tags":["a","b"],"debug":false}"#
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            get_merged_objects(
+                &sub_queries(&["defaults", "env", "local"]),
+                MergeStrategy::Concatenate,
+                &value,
+                RunnerOptions::default()
+            )
+            .map(|value| value.to_string()),
+            Ok(r#"{"db":{"host":"db.prod","port":5432},"tags":null,"debug":true}"#.to_string())
+        );
+        assert_eq!(
+            get_merged_objects(
+                &sub_queries(&["defaults", "list"]),
+                MergeStrategy::Replace,
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidObjectError(json!([])))
+        );
+    }
+
+    #[test]
+    fn check_get_object_set_operations() {
+        let left = json!({ "c": 1, "a": 2, "b": 3 });
+        let right = json!({ "b": 4, "d": 5, "c": 6 });
+        let (left, right) = (left.as_object().unwrap(), right.as_object().unwrap());
+
+        assert_eq!(
+            get_object_difference(left, right).to_string(),
+            json!({ "a": 2 }).to_string()
+        );
+        assert_eq!(
+            get_object_intersection(left, right).to_string(),
+            r#"{"c":6,"b":4}"#
+        );
+        assert_eq!(
+            get_object_union(left, right).to_string(),
+            r#"{"c":6,"a":2,"b":4,"d":5}"#
+        );
+    }
+
+    #[test]
+    fn check_get_object_has_key() {
+        assert_eq!(get_object_has_key("a", &json!({ "a": null })), json!(true));
+        assert_eq!(get_object_has_key("b", &json!({ "a": null })), json!(false));
+        assert_eq!(get_object_has_key("a", &json!(["a"])), json!(false));
+    }
+
     #[test]
     fn check_get_object_multi_key() {
         let value = json!({ "a": 1, "b": 2, "c": 3, "d": 4, "e": 5 });
@@ -393,6 +742,7 @@ mod tests {
             get_object_indexes(
                 &[Index::new(4), Index::new(2), Index::new(0)],
                 &mut value.clone(),
+                RunnerOptions::default(),
             ),
             json!({"e": 5, "c": 3, "a": 1}),
         );
@@ -404,7 +754,8 @@ mod tests {
             }),
             get_object_indexes(
                 &[Index::new(4), Index::new(2), Index::new(10)],
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             )
         );
     }
@@ -416,41 +767,57 @@ mod tests {
         assert_eq!(
             get_object_range(
                 &Range::new(Some(Index::new(0)), Some(Index::new(2))),
-                &mut json!({})
+                &mut json!({}),
+                RunnerOptions::default()
             ),
             Ok(json!({}))
         );
         assert_eq!(
             get_object_range(
                 &Range::new(Some(Index::new(0)), Some(Index::new(2))),
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!({ "a": 1, "b": 2, "c": 3 }))
         );
         assert_eq!(
             get_object_range(
                 &Range::new(Some(Index::new(2)), Some(Index::new(0))),
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!({ "c": 3, "b": 2, "a": 1 }))
         );
         assert_eq!(
             get_object_range(
                 &Range::new(Some(Index::new(0)), Some(Index::new(0))),
-                &mut value.clone()
+                &mut value.clone(),
+                RunnerOptions::default()
             ),
             Ok(json!({ "a": 1 }))
         );
         assert_eq!(
-            get_object_range(&Range::new(None, Some(Index::new(4))), &mut value.clone()),
+            get_object_range(
+                &Range::new(None, Some(Index::new(4))),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Ok(json!({ "a": 1, "b": 2, "c": 3, "d": 4, "e": 5 }))
         );
         assert_eq!(
-            get_object_range(&Range::new(Some(Index::new(4)), None), &mut value.clone()),
+            get_object_range(
+                &Range::new(Some(Index::new(4)), None),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Ok(json!({ "e": 5 }))
         );
         assert_eq!(
-            get_object_range(&Range::new(None, Some(Index::new(5))), &mut value.clone()),
+            get_object_range(
+                &Range::new(None, Some(Index::new(5))),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Err(KjqlRunnerError::RangeOutOfBoundsError {
                 start: 0,
                 end: 5,
@@ -460,11 +827,153 @@ mod tests {
 
         let value = json!(1);
         assert_eq!(
-            get_object_range(&Range::new(None, Some(Index::new(5))), &mut value.clone()),
+            get_object_range(
+                &Range::new(None, Some(Index::new(5))),
+                &mut value.clone(),
+                RunnerOptions::default()
+            ),
             Err(KjqlRunnerError::InvalidObjectError(value))
         );
     }
 
+    #[test]
+    fn check_get_object_selections() {
+        let value = json!({ "a": 1, "b": 2, "c": 3, "d": 4, "e": 5 });
+
+        assert_string_eq(
+            get_object_selections(
+                &[
+                    Selection::Index(Index::new(4)),
+                    Selection::Range(Range::new(Some(Index::new(0)), Some(Index::new(1)))),
+                ],
+                &value,
+                RunnerOptions::default(),
+            ),
+            json!({ "e": 5, "a": 1, "b": 2 }),
+        );
+        assert_string_eq(
+            get_object_selections(
+                &[
+                    Selection::Range(Range::new(Some(Index::new(2)), Some(Index::new(0)))),
+                    Selection::Index(Index::new(1)),
+                    Selection::Index(Index::new(3)),
+                ],
+                &value,
+                RunnerOptions::default(),
+            ),
+            json!({ "c": 3, "b": 2, "a": 1, "d": 4 }),
+        );
+        assert_string_eq(
+            get_object_selections(
+                &[
+                    Selection::IndexFromEnd(Index::new(1)),
+                    Selection::Index(Index::new(0)),
+                ],
+                &value,
+                RunnerOptions::default(),
+            ),
+            json!({ "e": 5, "a": 1 }),
+        );
+        assert_eq!(
+            get_object_selections(
+                &[Selection::IndexFromEnd(Index::new(6))],
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::IndexFromEndOutOfBoundsError {
+                index: 6,
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_object_selections(
+                &[Selection::IndexFromEnd(Index::new(6))],
+                &value,
+                RunnerOptions { lenient: true }
+            ),
+            Ok(json!({}))
+        );
+        assert_eq!(
+            get_object_selections(
+                &[Selection::Index(Index::new(7))],
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::IndexOutOfBoundsError {
+                index: 7,
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_object_selections(
+                &[Selection::Index(Index::new(0))],
+                &json!([]),
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::InvalidObjectError(json!([])))
+        );
+    }
+
+    #[test]
+    fn check_get_object_key_range() {
+        let value = json!({ "a": 1, "b": 2, "c": 3, "d": 4, "e": 5 });
+
+        assert_eq!(
+            get_object_key_range(&KeyRange::new("b", "d", true), &value),
+            Ok(json!({ "b": 2, "c": 3, "d": 4 }))
+        );
+        assert_eq!(
+            get_object_key_range(&KeyRange::new("b", "d", false), &value),
+            Ok(json!({ "b": 2, "c": 3 }))
+        );
+        assert_string_eq(
+            get_object_key_range(&KeyRange::new("d", "b", true), &value),
+            json!({ "d": 4, "c": 3, "b": 2 }),
+        );
+        assert_eq!(
+            get_object_key_range(&KeyRange::new("c", "c", false), &value),
+            Ok(json!({}))
+        );
+        assert_eq!(
+            get_object_key_range(&KeyRange::new("b", "z", true), &value),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "z".to_string(),
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_object_key_range(&KeyRange::new("a", "b", true), &json!([])),
+            Err(KjqlRunnerError::InvalidObjectError(json!([])))
+        );
+    }
+
+    #[test]
+    fn check_get_object_lenient() {
+        let value = json!({ "a": 1, "b": 2, "c": 3 });
+        let options = RunnerOptions { lenient: true };
+
+        assert_eq!(
+            get_object_indexes(&[Index::new(2), Index::new(5)], &mut value.clone(), options),
+            Ok(json!({ "c": 3 }))
+        );
+        assert_eq!(
+            get_object_range(
+                &Range::new(Some(Index::new(1)), Some(Index::new(10))),
+                &mut value.clone(),
+                options
+            ),
+            Ok(json!({ "b": 2, "c": 3 }))
+        );
+        assert_eq!(
+            get_object_range(
+                &Range::new(Some(Index::new(5)), None),
+                &mut value.clone(),
+                options
+            ),
+            Ok(json!({}))
+        );
+    }
+
     #[test]
     fn check_get_object_as_keys() {
         let value = json!({"a": 1, "b": 2, "c": 3, "d": 4, "e": 5});
@@ -473,4 +982,16 @@ mod tests {
             json!(["a", "b", "c", "d", "e"]),
         )
     }
+
+    #[test]
+    fn check_get_object_values() {
+        assert_string_eq(
+            get_object_values(&json!({"b": 1, "a": [2], "c": null})),
+            json!([1, [2], null]),
+        );
+        assert_eq!(
+            get_object_values(&json!(1)),
+            Err(KjqlRunnerError::InvalidObjectError(json!(1)))
+        );
+    }
 }
@@ -0,0 +1,552 @@
+use kjql_parser::{
+    group::split,
+    pointer::{
+        escape_reference,
+        reference_to_index,
+    },
+    tokens::Token,
+};
+use serde_json::{
+    Value,
+    json,
+};
+
+use crate::{
+    array::is_matching_lenses,
+    errors::KjqlRunnerError,
+    jsonpath::{
+        get_nodes,
+        is_singular,
+    },
+    runner::{
+        RunnerOptions,
+        group_runner_with_options,
+    },
+    value::get_json_type,
+};
+
+/// Location of a value as a JSON Pointer and a reference of the JSON `Value`.
+type Location<'a> = (String, &'a Value);
+
+/// Locations selected by the tokens.
+/// `Many` behaves like the array built by the runner out of a multiple
+/// selection.
+enum Selected<'a> {
+    /// A single location.
+    One(Location<'a>),
+    /// Multiple locations.
+    Many(Vec<Location<'a>>),
+}
+
+impl<'a> Selected<'a> {
+    /// Returns the selected value as built by the runner.
+    fn to_value(&self) -> Value {
+        match self {
+            Selected::One((_, value)) => (*value).clone(),
+            Selected::Many(locations) => json!(
+                locations
+                    .iter()
+                    .map(|(_, value)| (*value).clone())
+                    .collect::<Vec<Value>>()
+            ),
+        }
+    }
+
+    /// Returns the locations usable as the elements of an array, if any.
+    fn to_elements(&self) -> Option<Vec<Location<'a>>> {
+        match self {
+            Selected::One((pointer, Value::Array(array))) => Some(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| (format!("{pointer}/{index}"), value))
+                    .collect(),
+            ),
+            Selected::One(_) => None,
+            Selected::Many(locations) => Some(locations.clone()),
+        }
+    }
+
+    /// Returns the locations as a vector.
+    fn into_locations(self) -> Vec<Location<'a>> {
+        match self {
+            Selected::One(location) => vec![location],
+            Selected::Many(locations) => locations,
+        }
+    }
+
+    /// Returns the JSON Pointers of the locations.
+    fn into_pointers(self) -> Vec<String> {
+        self.into_locations()
+            .into_iter()
+            .map(|(pointer, _)| pointer)
+            .collect()
+    }
+}
+
+/// Takes a `Token`, the selected locations and the `RunnerOptions`.
+/// Returns the error produced by the runner for the token on the selected
+/// value, or the fallback error if the runner succeeds.
+fn get_runner_error(
+    token: &Token,
+    selected: &Selected,
+    options: RunnerOptions,
+    fallback: KjqlRunnerError,
+) -> KjqlRunnerError {
+    group_runner_with_options(&[token], &selected.to_value(), options)
+        .err()
+        .unwrap_or(fallback)
+}
+
+/// Takes an array index selector `Token`, the elements of an array and the
+/// `RunnerOptions`.
+/// Returns the locations of the selected elements or an error.
+/// The selector is applied on the indexes of the elements so that the runner
+/// semantics are kept.
+fn select_elements<'a>(
+    token: &Token,
+    elements: Vec<Location<'a>>,
+    options: RunnerOptions,
+) -> Result<Selected<'a>, KjqlRunnerError> {
+    let indexes = json!((0..elements.len()).collect::<Vec<usize>>());
+    let get_element = |index: &Value| {
+        // We can safely unwrap here since the indexes are numbers in bounds.
+        elements[usize::try_from(index.as_u64().unwrap()).unwrap()].clone()
+    };
+
+    match group_runner_with_options(&[token], &indexes, options)? {
+        Value::Array(selection) => Ok(Selected::Many(selection.iter().map(get_element).collect())),
+        Value::Null => Ok(Selected::Many(vec![])),
+        index => Ok(Selected::One(get_element(&index))),
+    }
+}
+
+/// Takes a `Token`, the selected locations, the `RunnerOptions` and a
+/// function selecting locations out of elements.
+/// Returns the locations selected out of the elements of the selected array,
+/// or the runner error if the selected value isn't an array.
+fn map_elements<'a>(
+    token: &Token,
+    selected: &Selected<'a>,
+    options: RunnerOptions,
+    map: impl FnOnce(Vec<Location<'a>>) -> Selected<'a>,
+) -> Result<Selected<'a>, KjqlRunnerError> {
+    match selected.to_elements() {
+        Some(elements) => Ok(map(elements)),
+        None => Err(get_runner_error(
+            token,
+            selected,
+            options,
+            KjqlRunnerError::UnknownError,
+        )),
+    }
+}
+
+/// Takes a `Token`, the selected locations and the `RunnerOptions`.
+/// Returns the locations selected by the token or an error.
+fn select<'a>(
+    token: &Token,
+    selected: Selected<'a>,
+    options: RunnerOptions,
+) -> Result<Selected<'a>, KjqlRunnerError> {
+    match (token, &selected) {
+        // References select a key on objects and an index on arrays.
+        (Token::ReferenceSelector(reference), Selected::One((_, Value::Object(_)))) => {
+            select(&Token::KeySelector(reference), selected, options)
+        }
+        (Token::ReferenceSelector(reference), _) => match reference_to_index(reference) {
+            Some(index) => select(&Token::ArrayIndexSelector(vec![index]), selected, options),
+            None => select(&Token::KeySelector(reference), selected, options),
+        },
+        (Token::JsonPathSelector(segments), _) => {
+            let singular = matches!(selected, Selected::One(_)) && is_singular(segments);
+            let mut nodes = selected
+                .into_locations()
+                .into_iter()
+                .flat_map(|location| get_nodes(segments, location))
+                .collect::<Vec<Location>>();
+
+            // A missing node of a singular path has no JSON Pointer.
+            match (singular, nodes.pop()) {
+                (true, Some(node)) => Ok(Selected::One(node)),
+                (_, node) => {
+                    nodes.extend(node);
+
+                    Ok(Selected::Many(nodes))
+                }
+            }
+        }
+        (Token::KeySelector(key), Selected::One((pointer, Value::Object(object)))) => {
+            match object.get_key_value(*key) {
+                Some((key, value)) => Ok(Selected::One((
+                    format!("{pointer}/{}", escape_reference(key)),
+                    value,
+                ))),
+                None => Err(get_runner_error(
+                    token,
+                    &selected,
+                    options,
+                    KjqlRunnerError::UnknownError,
+                )),
+            }
+        }
+        (
+            Token::MultiKeySelector(_)
+            | Token::ObjectIndexSelector(_)
+            | Token::ObjectKeyRangeSelector(_)
+            | Token::ObjectMixedSelector(_)
+            | Token::ObjectRangeSelector(_),
+            Selected::One((pointer, Value::Object(object))),
+        ) => {
+            let Value::Object(result) =
+                group_runner_with_options(&[token], &selected.to_value(), options)?
+            else {
+                return Err(KjqlRunnerError::UnknownError);
+            };
+
+            Ok(Selected::Many(
+                result
+                    .keys()
+                    .filter_map(|key| object.get_key_value(key))
+                    .map(|(key, value)| (format!("{pointer}/{}", escape_reference(key)), value))
+                    .collect(),
+            ))
+        }
+        (
+            Token::ArrayIndexSelector(_)
+            | Token::ArrayMixedSelector(_)
+            | Token::ArrayRangeSelector(_),
+            _,
+        ) => match selected.to_elements() {
+            Some(elements) => select_elements(token, elements, options)
+                .map_err(|error| get_runner_error(token, &selected, options, error)),
+            None => Err(get_runner_error(
+                token,
+                &selected,
+                options,
+                KjqlRunnerError::UnsupportedPointerError(token.to_string()),
+            )),
+        },
+        (Token::LensSelector(lenses), _) => map_elements(token, &selected, options, |elements| {
+            Selected::Many(
+                elements
+                    .into_iter()
+                    .filter(|(_, value)| is_matching_lenses(lenses, value, options))
+                    .collect(),
+            )
+        }),
+        // The first and last elements of an empty array are null.
+        (Token::FirstOperator, _) => map_elements(token, &selected, options, |elements| {
+            elements
+                .into_iter()
+                .next()
+                .map_or(Selected::Many(vec![]), Selected::One)
+        }),
+        (Token::LastOperator, _) => map_elements(token, &selected, options, |elements| {
+            elements
+                .into_iter()
+                .last()
+                .map_or(Selected::Many(vec![]), Selected::One)
+        }),
+        (Token::LimitOperator(count), _) => map_elements(token, &selected, options, |elements| {
+            Selected::Many(elements.into_iter().take(*count).collect())
+        }),
+        (Token::SkipOperator(count), _) => map_elements(token, &selected, options, |elements| {
+            Selected::Many(elements.into_iter().skip(*count).collect())
+        }),
+        (Token::ValuesOperator, Selected::One((pointer, Value::Object(object)))) => {
+            Ok(Selected::Many(
+                object
+                    .iter()
+                    .map(|(key, value)| (format!("{pointer}/{}", escape_reference(key)), value))
+                    .collect(),
+            ))
+        }
+        // The values of an array are the array itself.
+        (Token::ValuesOperator, Selected::One((_, Value::Array(_))) | Selected::Many(_)) => {
+            Ok(selected)
+        }
+        (Token::ValuesOperator, _) => Err(get_runner_error(
+            token,
+            &selected,
+            options,
+            KjqlRunnerError::UnknownError,
+        )),
+        (Token::TypeSelector(json_type), _) => {
+            map_elements(token, &selected, options, |elements| {
+                Selected::Many(
+                    elements
+                        .into_iter()
+                        .filter(|(_, value)| get_json_type(value) == *json_type)
+                        .collect(),
+                )
+            })
+        }
+        (
+            Token::KeySelector(_)
+            | Token::MultiKeySelector(_)
+            | Token::ObjectIndexSelector(_)
+            | Token::ObjectKeyRangeSelector(_)
+            | Token::ObjectMixedSelector(_)
+            | Token::ObjectRangeSelector(_),
+            _,
+        ) => Err(get_runner_error(
+            token,
+            &selected,
+            options,
+            KjqlRunnerError::UnknownError,
+        )),
+        _ => Err(KjqlRunnerError::UnsupportedPointerError(token.to_string())),
+    }
+}
+
+/// Takes a slice of references of `Token`, a reference of a JSON `Value` and
+/// the `RunnerOptions`.
+/// Returns the locations selected by the group or an error.
+fn select_group<'a>(
+    tokens: &[&Token],
+    json: &'a Value,
+    options: RunnerOptions,
+) -> Result<Selected<'a>, KjqlRunnerError> {
+    let (selected, _) = tokens.iter().try_fold(
+        (Selected::One((String::new(), json)), false),
+        |(selected, piped), token| match token {
+            Token::PipeInOperator => match selected.to_elements() {
+                Some(elements) => Ok((Selected::Many(elements), true)),
+                None => Err(KjqlRunnerError::PipeInError(selected.to_value())),
+            },
+            Token::PipeOutOperator => {
+                if piped {
+                    Ok((selected, false))
+                } else {
+                    Err(KjqlRunnerError::PipeOutError)
+                }
+            }
+            _ => {
+                if let (true, Selected::Many(locations)) = (piped, &selected) {
+                    let locations = locations
+                        .iter()
+                        .map(|location| {
+                            select(token, Selected::One(location.clone()), options)
+                                .map(Selected::into_locations)
+                        })
+                        .collect::<Result<Vec<Vec<Location>>, KjqlRunnerError>>()?;
+
+                    return Ok((Selected::Many(locations.concat()), piped));
+                }
+
+                select(token, selected, options).map(|selected| (selected, piped))
+            }
+        },
+    )?;
+
+    Ok(selected)
+}
+
+/// Takes a slice of `Token`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns the JSON Pointers of the values selected by the tokens as a JSON
+/// `Value` or an error.
+/// Only the selectors, the pipe operators and the `first`, `last`, `limit`,
+/// `skip` and `values` operators are supported since the other tokens build
+/// new values which don't exist in the JSON `Value`.
+///
+/// # Errors
+///
+/// Returns a `KjqlRunnerError` on failure.
+pub fn get_pointers(
+    tokens: &[Token],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    // The whole JSON `Value` is selected without tokens.
+    if tokens.is_empty() {
+        return Ok(json!([""]));
+    }
+
+    let pointers = split(tokens)
+        .iter()
+        .map(|group| select_group(group, json, options).map(Selected::into_pointers))
+        .collect::<Result<Vec<Vec<String>>, KjqlRunnerError>>()?;
+
+    Ok(json!(pointers.concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use kjql_parser::{
+        jsonpath::parse_jsonpath,
+        parser::parse,
+        pointer::pointer_to_tokens,
+    };
+    use serde_json::{
+        Value,
+        json,
+    };
+
+    use super::get_pointers;
+    use crate::{
+        errors::KjqlRunnerError,
+        runner::RunnerOptions,
+    };
+
+    fn pointers(query: &str, json: &Value) -> Result<Value, KjqlRunnerError> {
+        get_pointers(&parse(query).unwrap(), json, RunnerOptions::default())
+    }
+
+    #[test]
+    fn check_get_pointers() {
+        let value = json!({
+            "spec": { "containers": [{ "image": "a" }, { "image": "b" }, { "name": "c" }] },
+            "a/b": { "m~n": 1 }
+        });
+
+        assert_eq!(
+            pointers(r#""spec""containers"[0]"image""#, &value),
+            Ok(json!(["/spec/containers/0/image"]))
+        );
+        assert_eq!(pointers(r#""a/b""m~n""#, &value), Ok(json!(["/a~1b/m~0n"])));
+        assert_eq!(
+            pointers(r#""spec""containers"[2:0][1]"#, &value),
+            Ok(json!(["/spec/containers/1"]))
+        );
+        assert_eq!(
+            pointers(r#""spec""containers"[0:1]|>"image"<|"#, &value),
+            Ok(json!([
+                "/spec/containers/0/image",
+                "/spec/containers/1/image"
+            ]))
+        );
+        assert_eq!(
+            pointers(r#""spec""containers"|={"name"}"#, &value),
+            Ok(json!(["/spec/containers/2"]))
+        );
+        assert_eq!(
+            pointers(r#"{"spec","a/b"}"#, &value),
+            Ok(json!(["/spec", "/a~1b"]))
+        );
+        assert_eq!(
+            pointers(r#""spec","a/b""#, &value),
+            Ok(json!(["/spec", "/a~1b"]))
+        );
+        assert_eq!(
+            get_pointers(&[], &value, RunnerOptions::default()),
+            Ok(json!([""]))
+        );
+    }
+
+    #[test]
+    fn check_get_pointers_numeric_keys() {
+        let value =
+            json!({ "responses": { "200": { "d": 1 }, "404": { "d": 2 } }, "a": [{ "0": 3 }] });
+        let references = |pointer: &[&'static str]| -> Vec<Cow<'static, str>> {
+            pointer
+                .iter()
+                .map(|reference| Cow::Borrowed(*reference))
+                .collect()
+        };
+
+        assert_eq!(
+            get_pointers(
+                &pointer_to_tokens(&references(&["responses", "404", "d"])),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!(["/responses/404/d"]))
+        );
+        assert_eq!(
+            get_pointers(
+                &pointer_to_tokens(&references(&["a", "0", "0"])),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!(["/a/0/0"]))
+        );
+    }
+
+    #[test]
+    fn check_get_pointers_jsonpath() {
+        let value = json!({ "n": [1, 2], "b": { "c": 1, "d/e": 2 }, "s": "str" });
+        let pointers = |path: &str| {
+            get_pointers(
+                &parse_jsonpath(path).unwrap(),
+                &value,
+                RunnerOptions::default(),
+            )
+        };
+
+        assert_eq!(pointers("$.n[0,7]"), Ok(json!(["/n/0"])));
+        assert_eq!(pointers("$.b['d/e','zz']"), Ok(json!(["/b/d~1e"])));
+        assert_eq!(pointers("$.s[0:2]"), Ok(json!([])));
+        assert_eq!(pointers("$.n[1:]"), Ok(json!(["/n/1"])));
+        assert_eq!(pointers("$.b[*]"), Ok(json!(["/b/c", "/b/d~1e"])));
+        assert_eq!(pointers("$.b.c"), Ok(json!(["/b/c"])));
+        assert_eq!(pointers("$.b.zz"), Ok(json!([])));
+        assert_eq!(pointers("$..[1]"), Ok(json!(["/n/1"])));
+    }
+
+    #[test]
+    fn check_get_pointers_operators() {
+        let value = json!({ "a": [{ "b": 1 }, { "b": 2 }, { "b": 3 }], "o": { "c": 1, "d": 2 } });
+
+        assert_eq!(pointers(r#""a"first"b""#, &value), Ok(json!(["/a/0/b"])));
+        assert_eq!(pointers(r#""a"last"#, &value), Ok(json!(["/a/2"])));
+        assert_eq!(pointers(r#""a"[1:2]last"#, &value), Ok(json!(["/a/2"])));
+        assert_eq!(
+            pointers(r#""a"limit(2)"#, &value),
+            Ok(json!(["/a/0", "/a/1"]))
+        );
+        assert_eq!(
+            pointers(r#""a"skip(1)|>"b"<|"#, &value),
+            Ok(json!(["/a/1/b", "/a/2/b"]))
+        );
+        assert_eq!(pointers(r#""a"skip(5)first"#, &value), Ok(json!([])));
+        assert_eq!(
+            pointers(r#""o"values"#, &value),
+            Ok(json!(["/o/c", "/o/d"]))
+        );
+        assert_eq!(pointers(r#""a"values"#, &value), Ok(json!(["/a"])));
+        assert_eq!(
+            pointers(r#""o"first"#, &value),
+            Err(KjqlRunnerError::InvalidArrayError(
+                json!({ "c": 1, "d": 2 })
+            ))
+        );
+        assert_eq!(
+            pointers(r#""a"[0]"b"values"#, &value),
+            Err(KjqlRunnerError::InvalidObjectError(json!(1)))
+        );
+    }
+
+    #[test]
+    fn check_get_pointers_errors() {
+        let value = json!({ "a": [1, 2] });
+
+        assert_eq!(
+            pointers(r#""b""#, &value),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "b".to_string(),
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            pointers(r#""a"[2]"#, &value),
+            Err(KjqlRunnerError::IndexOutOfBoundsError {
+                index: 2,
+                parent: json!([1, 2])
+            })
+        );
+        assert_eq!(
+            pointers(r#""a"[0:1]"b""#, &value),
+            Err(KjqlRunnerError::InvalidObjectError(json!([1, 2])))
+        );
+        assert_eq!(
+            pointers(r#""a"sum"#, &value),
+            Err(KjqlRunnerError::UnsupportedPointerError(
+                "SumOperator".to_string()
+            ))
+        );
+    }
+}
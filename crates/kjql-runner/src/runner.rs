@@ -1,7 +1,11 @@
 use kjql_parser::{
     group::split,
     parser::parse,
-    tokens::Token,
+    pointer::reference_to_index,
+    tokens::{
+        Number,
+        Token,
+    },
 };
 use rayon::prelude::*;
 use serde_json::{
@@ -12,22 +16,79 @@ use serde_json::{
 use crate::{
     array::{
         get_array_as_indexes,
+        get_array_average,
+        get_array_groups_by,
+        get_array_has_index,
         get_array_indexes,
         get_array_lenses,
+        get_array_max,
+        get_array_max_by,
+        get_array_min,
+        get_array_min_by,
+        get_array_of_type,
         get_array_range,
+        get_array_selections,
+        get_array_sum,
+        get_first_element,
         get_flattened_array,
+        get_last_element,
+        get_limited_array,
+        get_skipped_array,
+        get_sorted_array,
+        get_sorted_array_by,
+        get_transposed_array,
+        get_unique_array,
+        get_unique_array_by,
+        get_zipped_arrays,
+        get_zipped_objects,
     },
     errors::KjqlRunnerError,
+    expression::{
+        get_conditional,
+        get_expression,
+    },
+    jsonpath::get_jsonpath,
     object::{
         get_flattened_object,
+        get_merged_objects,
         get_object_as_keys,
+        get_object_has_key,
         get_object_indexes,
         get_object_key,
+        get_object_key_range,
         get_object_multi_key,
         get_object_range,
+        get_object_selections,
+        get_object_values,
+    },
+    string::{
+        get_from_json,
+        get_joined_strings,
+        get_lowercase_string,
+        get_replaced_string,
+        get_split_string,
+        get_string_indexes,
+        get_string_range,
+        get_string_selections,
+        get_to_json,
+        get_to_number,
+        get_to_string,
+        get_trimmed_string,
+        get_uppercase_string,
+    },
+    value::{
+        get_descendants,
+        get_json_type,
     },
 };
 
+/// Options of the runner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunnerOptions {
+    /// Clamps the ranges and skips the missing indexes instead of failing.
+    pub lenient: bool,
+}
+
 /// Takes a raw input as a slice string to parse and a reference of a JSON
 /// `Value`.
 /// Returns a JSON `Value`.
@@ -36,12 +97,27 @@ use crate::{
 ///
 /// Returns a `KjqlRunnerError` on failure.
 pub fn raw(input: &str, json: &Value) -> Result<Value, KjqlRunnerError> {
+    raw_with_options(input, json, RunnerOptions::default())
+}
+
+/// Takes a raw input as a slice string to parse, a reference of a JSON
+/// `Value` and the `RunnerOptions`.
+/// Returns a JSON `Value`.
+///
+/// # Errors
+///
+/// Returns a `KjqlRunnerError` on failure.
+pub fn raw_with_options(
+    input: &str,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
     if input.is_empty() {
         return Err(KjqlRunnerError::EmptyQueryError);
     }
 
     let tokens = parse(input)?;
-    token(&tokens, json)
+    token_with_options(&tokens, json, options)
 }
 
 /// Takes a slice of `Tokens` to parse and a reference of a JSON
@@ -52,12 +128,27 @@ pub fn raw(input: &str, json: &Value) -> Result<Value, KjqlRunnerError> {
 ///
 /// Returns a `KjqlRunnerError` on failure.
 pub fn token(tokens: &[Token], json: &Value) -> Result<Value, KjqlRunnerError> {
+    token_with_options(tokens, json, RunnerOptions::default())
+}
+
+/// Takes a slice of `Tokens` to parse, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns a JSON `Value`.
+///
+/// # Errors
+///
+/// Returns a `KjqlRunnerError` on failure.
+pub fn token_with_options(
+    tokens: &[Token],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
     let groups = split(tokens);
 
     let result = groups
         .par_iter()
         .try_fold_with(vec![], |mut acc: Vec<Value>, group| {
-            acc.push(group_runner(group, json)?);
+            acc.push(group_runner_with_options(group, json, options)?);
             Ok::<Vec<Value>, KjqlRunnerError>(acc)
         })
         .try_reduce(Vec::new, |mut a, b| {
@@ -78,6 +169,19 @@ pub fn token(tokens: &[Token], json: &Value) -> Result<Value, KjqlRunnerError> {
 /// Note: The `GroupSeparator` enum variant is unreachable at this point since
 /// it has been filtered out by any of the public `runner` functions.
 pub fn group_runner(tokens: &[&Token], json: &Value) -> Result<Value, KjqlRunnerError> {
+    group_runner_with_options(tokens, json, RunnerOptions::default())
+}
+
+/// Takes a slice of references of `Token`, a reference of a JSON `Value` and
+/// the `RunnerOptions`.
+/// Returns a JSON `Value` or an error.
+/// Note: The `GroupSeparator` enum variant is unreachable at this point since
+/// it has been filtered out by any of the public `runner` functions.
+pub fn group_runner_with_options(
+    tokens: &[&Token],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
     tokens
         .iter()
         // at this lever we can use rayon since every token is applied sequentially.as
@@ -91,7 +195,8 @@ pub fn group_runner(tokens: &[&Token], json: &Value) -> Result<Value, KjqlRunner
                     .try_fold_with(
                         (vec![], outer_acc.1),
                         |mut inner_acc: (Vec<Value>, bool), inner_value| {
-                            let result = matcher((inner_value.clone(), outer_acc.1), token)?;
+                            let result =
+                                matcher((inner_value.clone(), outer_acc.1), token, options)?;
                             inner_acc.0.push(result.0);
                             inner_acc.1 = result.1;
                             Ok::<(Vec<Value>, bool), KjqlRunnerError>(inner_acc)
@@ -108,7 +213,7 @@ pub fn group_runner(tokens: &[&Token], json: &Value) -> Result<Value, KjqlRunner
 
                 Ok((json!(result.0), result.1))
             } else {
-                matcher(outer_acc, token)
+                matcher(outer_acc, token, options)
             }
         })
         // Drop the `pipe` boolean flag.
@@ -121,15 +226,35 @@ pub fn group_runner(tokens: &[&Token], json: &Value) -> Result<Value, KjqlRunner
 fn matcher(
     (mut acc, mut piped): (Value, bool),
     token: &Token,
+    options: RunnerOptions,
 ) -> Result<(Value, bool), KjqlRunnerError> {
     let result = match token {
-        Token::ArrayIndexSelector(indexes) => get_array_indexes(indexes, &acc),
-        Token::ArrayRangeSelector(range) => get_array_range(range, &mut acc),
+        Token::ArrayIndexSelector(indexes) => match acc {
+            Value::String(_) => get_string_indexes(indexes, &acc, options),
+            _ => get_array_indexes(indexes, &acc, options),
+        },
+        Token::ArrayMixedSelector(selections) => match acc {
+            Value::String(_) => get_string_selections(selections, &acc, options),
+            _ => get_array_selections(selections, &acc, options),
+        },
+        Token::ArrayRangeSelector(range) => match acc {
+            Value::String(_) => get_string_range(range, &acc, options),
+            _ => get_array_range(range, &mut acc, options),
+        },
+        Token::AverageOperator => get_array_average(&mut acc),
+        Token::Conditional(lenses, consequent, alternative) => {
+            get_conditional(lenses, consequent, alternative, &acc, options)
+        }
+        Token::DescendantsOperator => Ok(get_descendants(&acc)),
+        Token::Expression(expression) => get_expression(expression, &acc, options),
+        Token::FirstOperator => get_first_element(&mut acc),
         Token::FlattenOperator => match acc {
             Value::Array(_) => get_flattened_array(&acc),
             Value::Object(_) => Ok(get_flattened_object(&acc)),
             _ => Err(KjqlRunnerError::FlattenError(acc)),
         },
+        Token::JoinOperator(separator) => get_joined_strings(separator, &acc),
+        Token::JsonPathSelector(segments) => Ok(get_jsonpath(segments, &acc)),
         Token::KeyOperator => match acc {
             Value::Array(_) => get_array_as_indexes(&acc),
             Value::Object(_) => get_object_as_keys(&mut acc),
@@ -139,12 +264,32 @@ fn matcher(
             Value::String(string) => Ok(json!(string)),
             Value::Null => Ok(json!(null)),
         },
+        Token::FromJsonOperator => get_from_json(&acc),
+        Token::GroupByOperator(tokens) => get_array_groups_by(tokens, &mut acc, options),
         Token::GroupSeparator => unreachable!(),
+        Token::HasIndexOperator(index) => Ok(get_array_has_index(*index, &acc)),
+        Token::HasKeyOperator(key) => Ok(get_object_has_key(key, &acc)),
         Token::KeySelector(key) => get_object_key(key, &acc),
-        Token::LensSelector(lenses) => get_array_lenses(lenses, &mut acc),
+        Token::LastOperator => get_last_element(&mut acc),
+        Token::LimitOperator(count) => get_limited_array(*count, &mut acc),
+        Token::LensSelector(lenses) => get_array_lenses(lenses, &mut acc, options),
+        Token::LowerOperator => get_lowercase_string(&acc),
+        Token::MaxByOperator(tokens) => get_array_max_by(tokens, &mut acc, options),
+        Token::MaxOperator => get_array_max(&mut acc),
+        Token::MergeOperator(sub_queries, strategy) => {
+            get_merged_objects(sub_queries, *strategy, &acc, options)
+        }
+        Token::MinByOperator(tokens) => get_array_min_by(tokens, &mut acc, options),
+        Token::MinOperator => get_array_min(&mut acc),
         Token::MultiKeySelector(keys) => get_object_multi_key(keys, &mut acc),
-        Token::ObjectIndexSelector(indexes) => get_object_indexes(indexes, &mut acc),
-        Token::ObjectRangeSelector(range) => get_object_range(range, &mut acc),
+        Token::NumberLiteral(number) => match number {
+            Number::Integer(integer) => Ok(json!(integer)),
+            Number::Float(float) => Ok(json!(float)),
+        },
+        Token::ObjectIndexSelector(indexes) => get_object_indexes(indexes, &mut acc, options),
+        Token::ObjectKeyRangeSelector(range) => get_object_key_range(range, &acc),
+        Token::ObjectMixedSelector(selections) => get_object_selections(selections, &acc, options),
+        Token::ObjectRangeSelector(range) => get_object_range(range, &mut acc, options),
         Token::PipeInOperator => {
             if !acc.is_array() {
                 return Err(KjqlRunnerError::PipeInError(acc));
@@ -162,11 +307,43 @@ fn matcher(
             piped = false;
             Ok(acc)
         }
+        Token::ReferenceSelector(reference) => match (&acc, reference_to_index(reference)) {
+            (Value::Array(_), Some(index)) => get_array_indexes(&[index], &acc, options),
+            _ => get_object_key(reference, &acc),
+        },
+        Token::ReplaceOperator(from, to) => get_replaced_string(from, to, &acc),
+        Token::SkipOperator(count) => get_skipped_array(*count, &mut acc),
+        Token::SortByOperator(tokens, order) => {
+            get_sorted_array_by(tokens, *order, &mut acc, options)
+        }
+        Token::SortOperator(order) => get_sorted_array(*order, &mut acc),
+        Token::UniqueByOperator(tokens) => get_unique_array_by(tokens, &mut acc, options),
+        Token::UniqueOperator => get_unique_array(&mut acc),
+        Token::UpperOperator => get_uppercase_string(&acc),
+        Token::ValuesOperator => match acc {
+            Value::Array(_) => Ok(acc),
+            _ => get_object_values(&acc),
+        },
+        Token::SplitOperator(separator) => get_split_string(separator, &acc),
+        Token::SumOperator => get_array_sum(&mut acc),
+        Token::ToJsonOperator => Ok(get_to_json(&acc)),
+        Token::ToNumberOperator => get_to_number(&acc),
+        Token::ToStringOperator => Ok(get_to_string(&acc)),
+        Token::TransposeOperator(alignment) => get_transposed_array(*alignment, &mut acc),
+        Token::TrimOperator => get_trimmed_string(&acc),
         Token::TruncateOperator => match acc {
             Value::Array(_) => Ok(json!([])),
             Value::Object(_) => Ok(json!({})),
             Value::Bool(_) | Value::Number(_) | Value::String(_) | Value::Null => Ok(acc),
         },
+        Token::ZipObjectOperator(sub_queries, alignment) => {
+            get_zipped_objects(sub_queries, *alignment, &acc, options)
+        }
+        Token::ZipOperator(sub_queries, alignment) => {
+            get_zipped_arrays(sub_queries, *alignment, &acc, options)
+        }
+        Token::TypeOperator => Ok(json!(get_json_type(&acc).to_string())),
+        Token::TypeSelector(json_type) => get_array_of_type(*json_type, &mut acc),
     };
 
     result.map(|value| (value, piped))
@@ -175,15 +352,28 @@ fn matcher(
 #[cfg(test)]
 mod tests {
     use kjql_parser::{
+        dotted::parse_dotted,
         errors::KjqlParserError,
+        jsonpath::parse_jsonpath,
         tokens::{
+            Index,
             Token,
             View,
         },
     };
-    use serde_json::json;
+    use serde_json::{
+        Value,
+        json,
+    };
 
-    use super::raw;
+    use super::{
+        RunnerOptions,
+        group_runner,
+        group_runner_with_options,
+        raw,
+        raw_with_options,
+        token,
+    };
     use crate::errors::KjqlRunnerError;
 
     #[test]
@@ -199,6 +389,8 @@ mod tests {
                 KjqlParserError::ParsingError {
                     tokens: [Token::KeySelector("a")].stringify(),
                     unparsed: "b".to_string(),
+                    offset: 3,
+                    expected: vec!["a selector or an operator".to_string()],
                 }
             ))
         );
@@ -286,9 +478,496 @@ mod tests {
         )
     }
 
+    #[test]
+    fn check_runner_sort() {
+        assert_eq!(raw("sort", &json!([3, 1, 2])), Ok(json!([1, 2, 3])));
+        assert_eq!(raw("sort_desc", &json!([3, 1, 2])), Ok(json!([3, 2, 1])));
+        assert_eq!(
+            raw(
+                r#""a"sort_by("b")|>"c""#,
+                &json!({ "a": [{ "b": 2, "c": "x" }, { "b": 1, "c": "y" }] })
+            ),
+            Ok(json!(["y", "x"]))
+        );
+    }
+
+    #[test]
+    fn check_runner_unique() {
+        assert_eq!(
+            raw(r#""a"|>unique"#, &json!({ "a": [[1, 1, 2], [3, 2, 3]] })),
+            Ok(json!([[1, 2], [3, 2]]))
+        );
+        assert_eq!(
+            raw("..unique", &json!([[1, [2, 1]], [3, [2]]])),
+            Ok(json!([1, 2, 3]))
+        );
+        assert_eq!(
+            raw(
+                r#"unique_by("a""b")"#,
+                &json!([{ "a": { "b": 1 }, "c": 1 }, { "a": { "b": 1 }, "c": 2 }])
+            ),
+            Ok(json!([{ "a": { "b": 1 }, "c": 1 }]))
+        );
+    }
+
+    #[test]
+    fn check_runner_group_by() {
+        assert_eq!(
+            raw(
+                r#""a"group_by("region")@"#,
+                &json!({ "a": [{ "region": "eu" }, { "region": "us" }, { "region": "eu" }] })
+            ),
+            Ok(json!(["eu", "us"]))
+        );
+    }
+
+    #[test]
+    fn check_runner_aggregates() {
+        let value = json!({ "metrics": [{ "ms": 10 }, { "ms": 30 }, { "ms": 20 }] });
+
+        assert_eq!(raw(r#""metrics"|>"ms"<|sum"#, &value), Ok(json!(60)));
+        assert_eq!(raw(r#""metrics"|>"ms"<|avg"#, &value), Ok(json!(20.0)));
+        assert_eq!(raw(r#""metrics"|>"ms"<|min"#, &value), Ok(json!(10)));
+        assert_eq!(raw(r#""metrics"|>"ms"<|max"#, &value), Ok(json!(30)));
+        assert_eq!(
+            raw(r#""metrics"min_by("ms")"#, &value),
+            Ok(json!({ "ms": 10 }))
+        );
+        assert_eq!(
+            raw(r#""metrics"max_by("ms")"#, &value),
+            Ok(json!({ "ms": 30 }))
+        );
+    }
+
+    #[test]
+    fn check_runner_arithmetic() {
+        let value = json!({
+            "bytes": 1536,
+            "items": [{ "price": 2, "qty": 3 }, { "price": 1.5, "qty": 2 }]
+        });
+
+        assert_eq!(raw(r#""bytes" / 1024"#, &value), Ok(json!(1.5)));
+        assert_eq!(raw(r#""bytes" / 512"#, &value), Ok(json!(3)));
+        assert_eq!(
+            raw(r#""items"|>"price" * "qty"<|"#, &value),
+            Ok(json!([6, 3.0]))
+        );
+        assert_eq!(
+            raw(r#""items"|>"price" * "qty"<|sum + 1"#, &value),
+            Ok(json!(10.0))
+        );
+        assert_eq!(raw("1 + 2 * 3 - 4 % 3", &value), Ok(json!(6)));
+        assert_eq!(
+            raw(r#""bytes" / 0"#, &value),
+            Err(KjqlRunnerError::DivisionByZeroError(json!(1536)))
+        );
+    }
+
+    #[test]
+    fn check_runner_types() {
+        let value = json!({
+            "items": [
+                { "id": "a1", "tags": ["x"] },
+                { "id": 2, "tags": "y" },
+                "skip",
+                { "id": "c3", "tags": null },
+            ]
+        });
+
+        assert_eq!(
+            raw(r#""items"<object>|>"id"type<|"#, &value),
+            Ok(json!(["string", "number", "string"]))
+        );
+        assert_eq!(
+            raw(r#""items"<object>|={"id"=<string>}|>"id"<|"#, &value),
+            Ok(json!(["a1", "c3"]))
+        );
+        assert_eq!(
+            raw(
+                r#""items"<object>|={"tags"=<array>,"tags"=<null>}|>"id"<|"#,
+                &value
+            ),
+            Ok(json!(["a1", "c3"]))
+        );
+        assert_eq!(raw(r#""items"<string>"#, &value), Ok(json!(["skip"])));
+        assert_eq!(raw(r#""items"type"#, &value), Ok(json!("array")));
+    }
+
+    #[test]
+    fn check_runner_slices() {
+        let value = json!({ "items": [{ "id": 1 }, { "id": 2 }, { "id": 3 }] });
+
+        assert_eq!(
+            raw(r#""items"limit(10)|>"id"<|"#, &value),
+            Ok(json!([1, 2, 3]))
+        );
+        assert_eq!(
+            raw(r#""items"skip(1)limit(1)|>"id"<|"#, &value),
+            Ok(json!([2]))
+        );
+        assert_eq!(raw(r#""items"first"id""#, &value), Ok(json!(1)));
+        assert_eq!(raw(r#""items"last"id""#, &value), Ok(json!(3)));
+        assert_eq!(raw(r#""items"skip(5)first"#, &value), Ok(json!(null)));
+    }
+
+    #[test]
+    fn check_runner_zip() {
+        let value = json!({
+            "metrics": { "ts": [10, 20], "value": [0.5, 0.7], "unit": ["ms"] },
+        });
+
+        assert_eq!(
+            raw(r#""metrics"zip_object("ts","value")"#, &value),
+            Ok(json!([{ "ts": 10, "value": 0.5 }, { "ts": 20, "value": 0.7 }]))
+        );
+        assert_eq!(
+            raw(r#"zip_pad("metrics""ts","metrics""unit")"#, &value),
+            Ok(json!([[10, "ms"], [20, null]]))
+        );
+        assert_eq!(
+            raw(r#""metrics"zip("ts","value")transpose"#, &value),
+            Ok(json!([[10, 20], [0.5, 0.7]]))
+        );
+        assert_eq!(
+            raw(r#""metrics"zip("ts","unit")"#, &value),
+            Err(KjqlRunnerError::LengthMismatchError {
+                lengths: vec![2, 1]
+            })
+        );
+    }
+
+    #[test]
+    fn check_runner_merge() {
+        let value = json!({
+            "defaults": { "log": { "level": "info", "targets": ["stdout"] }, "port": 80 },
+            "environment": { "log": { "targets": ["file"] } },
+            "local": { "port": 8080 },
+        });
+
+        assert_eq!(
+            raw(r#"merge("defaults","environment","local")"#, &value)
+                .map(|value| value.to_string()),
+            Ok(r#"{"log":{"level":"info","targets":["file"]},"port":8080}"#.to_string())
+        );
+        assert_eq!(
+            raw(
+                r#"merge_concat("defaults","environment")"log""targets""#,
+                &value
+            ),
+            Ok(json!(["stdout", "file"]))
+        );
+    }
+
+    #[test]
+    fn check_runner_set_operations() {
+        let value = json!({
+            "allowed": ["read", "write", "admin"],
+            "used": ["write", "read", "write"],
+            "defaults": { "color": "blue", "size": 1 },
+            "local": { "size": 2, "debug": true },
+        });
+
+        assert_eq!(raw(r#""allowed" - "used""#, &value), Ok(json!(["admin"])));
+        assert_eq!(
+            raw(r#""used" & "allowed""#, &value),
+            Ok(json!(["write", "read"]))
+        );
+        assert_eq!(
+            raw(r#""used" | "allowed","allowed" - "used" | "used""#, &value),
+            Ok(json!([
+                ["write", "read", "admin"],
+                ["admin", "write", "read"]
+            ]))
+        );
+        assert_eq!(
+            raw(r#""defaults" | "local""#, &value).map(|value| value.to_string()),
+            Ok(r#"{"color":"blue","size":2,"debug":true}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn check_runner_has() {
+        let value = json!({
+            "users": [
+                { "name": "a", "email": "a@b.c", "roles": ["admin"] },
+                { "name": "b", "roles": [] },
+            ]
+        });
+
+        assert_eq!(
+            raw(r#""users"|>has("email")<|"#, &value),
+            Ok(json!([true, false]))
+        );
+        assert_eq!(
+            raw(r#""users"|>"roles"has(0)<|"#, &value),
+            Ok(json!([true, false]))
+        );
+        assert_eq!(
+            raw(r#""users"|={"email"=exists}|>"name"<|"#, &value),
+            Ok(json!(["a"]))
+        );
+        assert_eq!(
+            raw(
+                r#""users"|>if "email"=exists then "email" else "name" end<|"#,
+                &value
+            ),
+            Ok(json!(["a@b.c", "b"]))
+        );
+    }
+
+    #[test]
+    fn check_runner_conditional() {
+        let value = json!({
+            "records": [
+                { "status": "ok", "primary": "a", "fallback": "b" },
+                { "status": "ko", "primary": "c", "fallback": "d" },
+                { "primary": null, "fallback": "e" },
+            ]
+        });
+
+        assert_eq!(
+            raw(
+                r#""records"|>if "status"="ok" then "primary" else "fallback" end<|"#,
+                &value
+            ),
+            Ok(json!(["a", "d", "e"]))
+        );
+        assert_eq!(
+            raw(
+                r#""records"|>if "primary"=<string> then "primary" upper else "fallback" end<|"#,
+                &value
+            ),
+            Ok(json!(["A", "C", "e"]))
+        );
+        assert_eq!(
+            raw(
+                r#""records"[0]if "status" then "primary","fallback" else "status" end"#,
+                &value
+            ),
+            Ok(json!(["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn check_runner_strings() {
+        let value = json!({ "tags": " Rust,JSON , CLI ", "sha": "3f2a9c1d8e" });
+
+        assert_eq!(
+            raw(r#""tags"split(",")|>trim lower<|join("|")"#, &value),
+            Ok(json!("rust|json|cli"))
+        );
+        assert_eq!(
+            raw(r#""tags"trim replace(",", ";")upper"#, &value),
+            Ok(json!("RUST;JSON ; CLI"))
+        );
+        assert_eq!(raw(r#""sha"[0:6]"#, &value), Ok(json!("3f2a9c1")));
+        assert_eq!(
+            raw(r#""sha"lower upper split(",")[0]"#, &value),
+            Ok(json!("3F2A9C1D8E"))
+        );
+    }
+
+    #[test]
+    fn check_runner_conversions() {
+        let value = json!({
+            "events": [
+                { "payload": r#"{ "user": { "id": 1 } }"#, "count": "2" },
+                { "payload": r#"{ "user": { "id": 2 } }"#, "count": "3" },
+            ]
+        });
+
+        assert_eq!(
+            raw(r#""events"|>"payload"fromjson"user""id"<|"#, &value),
+            Ok(json!([1, 2]))
+        );
+        assert_eq!(
+            raw(r#""events"|>"count"tonumber<|sum tostring"#, &value),
+            Ok(json!("5"))
+        );
+        assert_eq!(
+            raw(r#""events"[0]"payload"fromjson tojson"#, &value),
+            Ok(json!(r#"{"user":{"id":1}}"#))
+        );
+    }
+
     #[test]
     fn check_runner_keys() {
         let value = json!({"a": { "b": { "c": {"d": 1 }} }});
         assert_eq!(Ok(json!(["d"])), raw(r#""a""b""c"@"#, &value));
     }
+
+    #[test]
+    fn check_runner_string_indexing() {
+        let value = json!({ "sha": "4c33181a9e", "message": "Add a feature" });
+
+        assert_eq!(raw(r#""sha"[0:6]"#, &value), Ok(json!("4c33181")));
+        assert_eq!(raw(r#""sha"[0]"#, &value), Ok(json!("4")));
+        assert_eq!(raw(r#""message"[4:0]"#, &value), Ok(json!("a ddA")));
+        assert_eq!(raw(r#""message"[0:2,12]"#, &value), Ok(json!("Adde")));
+    }
+
+    #[test]
+    fn check_runner_reference_selector() {
+        let value = json!({ "versions": { "200": "ok" }, "a": ["x", "y"], "b": { "01": 1 } });
+
+        assert_eq!(
+            token(
+                &[
+                    Token::KeySelector("versions"),
+                    Token::ReferenceSelector("200")
+                ],
+                &value
+            ),
+            Ok(json!("ok"))
+        );
+        assert_eq!(
+            token(
+                &[Token::KeySelector("a"), Token::ReferenceSelector("1")],
+                &value
+            ),
+            Ok(json!("y"))
+        );
+        assert_eq!(
+            token(
+                &[Token::KeySelector("a"), Token::ReferenceSelector("01")],
+                &value
+            ),
+            Err(KjqlRunnerError::InvalidObjectError(json!(["x", "y"])))
+        );
+        assert_eq!(
+            token(
+                &[Token::KeySelector("b"), Token::ReferenceSelector("01")],
+                &value
+            ),
+            Ok(json!(1))
+        );
+
+        let dotted = |path| token(&parse_dotted(path).unwrap(), &value);
+
+        assert_eq!(dotted("versions.200"), Ok(json!("ok")));
+        assert_eq!(dotted("a.1"), Ok(json!("y")));
+    }
+
+    #[test]
+    fn check_group_runner() {
+        let value = json!({ "a": [1, 2] });
+        let tokens = [
+            Token::KeySelector("a"),
+            Token::ArrayIndexSelector(vec![Index::new(5)]),
+        ];
+        let group: Vec<&Token> = tokens.iter().collect();
+
+        assert_eq!(group_runner(&group[..1], &value), Ok(json!([1, 2])));
+        assert!(group_runner(&group, &value).is_err());
+        assert_eq!(
+            group_runner_with_options(&group, &value, RunnerOptions { lenient: true }),
+            Ok(json!(null))
+        );
+    }
+
+    #[test]
+    fn check_runner_mixed_selectors() {
+        let value = json!({ "a": [0, 1, 2, 3, 4, 5, 6, 7], "b": { "c": 1, "d": 2, "e": 3 } });
+
+        assert_eq!(raw(r#""a"[0, 3:5, 7]"#, &value), Ok(json!([0, 3, 4, 5, 7])));
+        assert_eq!(raw(r#""a"[2:0,6]"#, &value), Ok(json!([2, 1, 0, 6])));
+        assert_eq!(
+            raw(r#""a"[0, 3:6, -1]"#, &value),
+            Ok(json!([0, 3, 4, 5, 6, 7]))
+        );
+        assert_eq!(raw(r#""a"[-2]"#, &value), Ok(json!([6])));
+        assert_eq!(raw(r#""b"{-1}"#, &value), Ok(json!({ "e": 3 })));
+        assert_eq!(raw(r#""b" "c" tostring [-1]"#, &value), Ok(json!("1")));
+        assert_eq!(
+            raw(r#""b"{2, 0:1}"#, &value),
+            Ok(json!({ "e": 3, "c": 1, "d": 2 }))
+        );
+    }
+
+    #[test]
+    fn check_runner_object_key_range() {
+        let value = json!({ "a": { "b": 1, "c": 2, "d": 3 } });
+
+        assert_eq!(
+            raw(r#""a"{"c":"d"}"#, &value),
+            Ok(json!({ "c": 2, "d": 3 }))
+        );
+        assert_eq!(
+            raw(r#""a"{"b".."d"}"#, &value),
+            Ok(json!({ "b": 1, "c": 2 }))
+        );
+        assert_eq!(
+            raw(r#""a"{"b":"e"}"#, &value),
+            Err(KjqlRunnerError::KeyNotFoundError {
+                key: "e".to_string(),
+                parent: json!({ "b": 1, "c": 2, "d": 3 })
+            })
+        );
+    }
+
+    #[test]
+    fn check_runner_lenient() {
+        let value = json!({ "a": [1, 2, 3] });
+        let options = RunnerOptions { lenient: true };
+
+        assert_eq!(
+            raw_with_options(r#""a"[1:10]"#, &value, options),
+            Ok(json!([2, 3]))
+        );
+        assert_eq!(
+            raw_with_options(r#""a"[0,7]"#, &value, options),
+            Ok(json!([1]))
+        );
+        assert_eq!(
+            raw_with_options(r#""a"[7]"#, &value, options),
+            Ok(Value::Null)
+        );
+        assert!(raw(r#""a"[1:10]"#, &value).is_err());
+    }
+
+    #[test]
+    fn check_runner_values_and_descendants() {
+        let value = json!({ "a": { "b": 1, "c": [2] } });
+
+        assert_eq!(raw(r#""a"values"#, &value), Ok(json!([1, [2]])));
+        assert_eq!(raw(r#""a""c"values"#, &value), Ok(json!([2])));
+        assert_eq!(
+            raw(r#""a""b"values"#, &value),
+            Err(KjqlRunnerError::InvalidObjectError(json!(1)))
+        );
+        assert_eq!(
+            raw(r#""a"descendants"#, &value),
+            Ok(json!([{ "b": 1, "c": [2] }, 1, [2], 2]))
+        );
+    }
+
+    #[test]
+    fn check_runner_jsonpath() {
+        let value = json!({
+            "a": [1, 2, 3],
+            "store": { "book": [{ "isbn": "1", "title": "x" }, { "title": "y" }] }
+        });
+        let jsonpath = |path| token(&parse_jsonpath(path).unwrap(), &value);
+
+        assert_eq!(jsonpath("$.a[1:10]"), Ok(json!([2, 3])));
+        assert_eq!(jsonpath("$.a[5:]"), Ok(json!([])));
+        assert_eq!(jsonpath("$.a[1]"), Ok(json!(2)));
+        assert_eq!(jsonpath("$.a[5]"), Ok(json!(null)));
+        assert_eq!(jsonpath("$.b[*]"), Ok(json!([])));
+        assert_eq!(jsonpath("$.store.book[*].isbn"), Ok(json!(["1"])));
+        assert_eq!(
+            jsonpath("$..book[?(@.isbn)]"),
+            Ok(json!([{ "isbn": "1", "title": "x" }]))
+        );
+        assert_eq!(jsonpath("$..book[?(@.isbn)].title"), Ok(json!(["x"])));
+
+        let value = json!({ "n": [1, 2], "b": { "c": 1, "d": 2 }, "s": "str" });
+        let jsonpath = |path| token(&parse_jsonpath(path).unwrap(), &value);
+
+        assert_eq!(jsonpath("$.n[0,7]"), Ok(json!([1])));
+        assert_eq!(jsonpath("$.b['c','zz']"), Ok(json!([1])));
+        assert_eq!(jsonpath("$.s[0:2]"), Ok(json!([])));
+        assert_eq!(jsonpath("$.b[1:2]"), Ok(json!([])));
+        assert_eq!(jsonpath("$.s[*]"), Ok(json!([])));
+    }
 }
@@ -0,0 +1,560 @@
+use std::num::NonZeroUsize;
+
+use kjql_parser::tokens::{
+    Index,
+    Range,
+    Selection,
+};
+use serde_json::{
+    Number,
+    Value,
+    json,
+};
+
+use crate::{
+    errors::KjqlRunnerError,
+    runner::RunnerOptions,
+};
+
+/// Takes a reference of a JSON `Value` and returns a string slice or an error.
+fn as_str(json: &Value) -> Result<&str, KjqlRunnerError> {
+    json.as_str()
+        .ok_or_else(|| KjqlRunnerError::InvalidStringError(json.clone()))
+}
+
+/// Takes a separator as a string slice and a reference of a JSON `Value`.
+/// Returns an array of substrings as a JSON `Value` or an error.
+/// An empty separator splits the string into its characters.
+pub(crate) fn get_split_string(separator: &str, json: &Value) -> Result<Value, KjqlRunnerError> {
+    let string = as_str(json)?;
+
+    if separator.is_empty() {
+        return Ok(json!(
+            string.chars().map(String::from).collect::<Vec<String>>()
+        ));
+    }
+
+    Ok(json!(string.split(separator).collect::<Vec<&str>>()))
+}
+
+/// Takes a separator as a string slice and a reference of a JSON `Value`.
+/// Returns an array of strings joined as a JSON `Value` or an error.
+pub(crate) fn get_joined_strings(separator: &str, json: &Value) -> Result<Value, KjqlRunnerError> {
+    let array = json
+        .as_array()
+        .ok_or_else(|| KjqlRunnerError::InvalidArrayError(json.clone()))?;
+
+    let strings = array
+        .iter()
+        .map(as_str)
+        .collect::<Result<Vec<&str>, KjqlRunnerError>>()?;
+
+    Ok(json!(strings.join(separator)))
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the lowercase string as a JSON `Value` or an error.
+pub(crate) fn get_lowercase_string(json: &Value) -> Result<Value, KjqlRunnerError> {
+    Ok(json!(as_str(json)?.to_lowercase()))
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the uppercase string as a JSON `Value` or an error.
+pub(crate) fn get_uppercase_string(json: &Value) -> Result<Value, KjqlRunnerError> {
+    Ok(json!(as_str(json)?.to_uppercase()))
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the string without leading and trailing whitespaces as a JSON
+/// `Value` or an error.
+pub(crate) fn get_trimmed_string(json: &Value) -> Result<Value, KjqlRunnerError> {
+    Ok(json!(as_str(json)?.trim()))
+}
+
+/// Takes a pattern and its replacement as string slices and a reference of a
+/// JSON `Value`.
+/// Returns the string with all the occurrences of the pattern replaced as a
+/// JSON `Value` or an error.
+pub(crate) fn get_replaced_string(
+    from: &str,
+    to: &str,
+    json: &Value,
+) -> Result<Value, KjqlRunnerError> {
+    Ok(json!(as_str(json)?.replace(from, to)))
+}
+
+/// Takes an `Index`, the characters of a string, a mutable reference of the
+/// resulting `String`, a reference of the JSON `Value` and the
+/// `RunnerOptions`.
+/// Pushes the character at the index to the result or returns an error.
+/// In lenient mode, a missing index is skipped.
+fn push_string_index(
+    index: Index,
+    chars: &[char],
+    result: &mut String,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<(), KjqlRunnerError> {
+    let num: usize = index.into();
+
+    match chars.get(num) {
+        Some(char) => result.push(*char),
+        None if options.lenient => {}
+        None => {
+            return Err(KjqlRunnerError::IndexOutOfBoundsError {
+                index: num,
+                parent: json.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Takes a slice of `Index`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns the characters at the indexes based on Unicode scalar values as a
+/// JSON `Value` or an error.
+pub(crate) fn get_string_indexes(
+    indexes: &[Index],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let chars: Vec<char> = as_str(json)?.chars().collect();
+
+    let result = indexes.iter().try_fold(String::new(), |mut acc, index| {
+        push_string_index(*index, &chars, &mut acc, json, options)?;
+
+        Ok::<String, KjqlRunnerError>(acc)
+    })?;
+
+    Ok(json!(result))
+}
+
+/// Takes a slice of `Selection`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns the selected substrings concatenated in the order of the
+/// selections as a JSON `Value` or an error.
+pub(crate) fn get_string_selections(
+    selections: &[Selection],
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let chars: Vec<char> = as_str(json)?.chars().collect();
+
+    let result = selections
+        .iter()
+        .try_fold(String::new(), |mut acc, selection| {
+            match selection {
+                Selection::Index(index) => {
+                    push_string_index(*index, &chars, &mut acc, json, options)?;
+                }
+                Selection::IndexFromEnd(index) => match index.from_end(chars.len()) {
+                    Some(index) => push_string_index(index, &chars, &mut acc, json, options)?,
+                    None if options.lenient => {}
+                    None => {
+                        return Err(KjqlRunnerError::IndexFromEndOutOfBoundsError {
+                            index: (*index).into(),
+                            parent: json.clone(),
+                        });
+                    }
+                },
+                Selection::Range(range) => {
+                    if let Value::String(substring) = get_string_range(range, json, options)? {
+                        acc.push_str(&substring);
+                    }
+                }
+            }
+
+            Ok::<String, KjqlRunnerError>(acc)
+        })?;
+
+    Ok(json!(result))
+}
+
+/// Takes a reference of a `Range`, a reference of a JSON `Value` and the
+/// `RunnerOptions`.
+/// Returns a substring based on Unicode scalar values as a JSON `Value` or an
+/// error.
+/// In lenient mode, the boundaries are clamped to the length of the string.
+pub(crate) fn get_string_range(
+    range: &Range,
+    json: &Value,
+    options: RunnerOptions,
+) -> Result<Value, KjqlRunnerError> {
+    let chars: Vec<char> = as_str(json)?.chars().collect();
+
+    if chars.is_empty() {
+        return Ok(json!(""));
+    }
+
+    let len = chars.len();
+    // String's length can't be zero so we can safely unwrap here.
+    let non_zero_len = NonZeroUsize::new(len).unwrap();
+    let (start, end) = range.to_boundaries(non_zero_len);
+
+    let (start, end) = if options.lenient {
+        match range.to_clamped_boundaries(non_zero_len) {
+            Some(boundaries) => boundaries,
+            None => return Ok(json!("")),
+        }
+    } else if start + 1 > len || end + 1 > len {
+        // Out of bounds.
+        return Err(KjqlRunnerError::RangeOutOfBoundsError {
+            start,
+            end,
+            parent: json.clone(),
+        });
+    } else {
+        (start, end)
+    };
+
+    let result: String = if start < end {
+        chars[start..=end].iter().collect()
+    } else {
+        chars[end..=start].iter().rev().collect()
+    };
+
+    Ok(json!(result))
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the string as is or the serialized value for any other type as a
+/// JSON `Value`.
+pub(crate) fn get_to_string(json: &Value) -> Value {
+    match json {
+        Value::String(_) => json.clone(),
+        _ => json!(json.to_string()),
+    }
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the number as is or the parsed string as a JSON `Value` or an
+/// error.
+pub(crate) fn get_to_number(json: &Value) -> Result<Value, KjqlRunnerError> {
+    match json {
+        Value::Number(_) => Ok(json.clone()),
+        Value::String(string) => string
+            .trim()
+            .parse::<Number>()
+            .map(Value::Number)
+            .map_err(|_| KjqlRunnerError::NumberConversionError(json.clone())),
+        _ => Err(KjqlRunnerError::NumberConversionError(json.clone())),
+    }
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the serialized value as a JSON `Value`.
+pub(crate) fn get_to_json(json: &Value) -> Value {
+    json!(json.to_string())
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the JSON `Value` embedded in the string or an error.
+pub(crate) fn get_from_json(json: &Value) -> Result<Value, KjqlRunnerError> {
+    serde_json::from_str(as_str(json)?).map_err(|error| KjqlRunnerError::FromJsonError {
+        value: json.clone(),
+        reason: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use kjql_parser::tokens::{
+        Index,
+        Range,
+        Selection,
+    };
+    use serde_json::json;
+
+    use super::{
+        get_from_json,
+        get_joined_strings,
+        get_lowercase_string,
+        get_replaced_string,
+        get_split_string,
+        get_string_indexes,
+        get_string_range,
+        get_string_selections,
+        get_to_json,
+        get_to_number,
+        get_to_string,
+        get_trimmed_string,
+        get_uppercase_string,
+    };
+    use crate::{
+        errors::KjqlRunnerError,
+        runner::RunnerOptions,
+    };
+
+    #[test]
+    fn check_get_split_string() {
+        assert_eq!(
+            get_split_string(",", &json!("a,b,,c")),
+            Ok(json!(["a", "b", "", "c"]))
+        );
+        assert_eq!(get_split_string("", &json!("aé")), Ok(json!(["a", "é"])));
+        assert_eq!(
+            get_split_string(",", &json!(1)),
+            Err(KjqlRunnerError::InvalidStringError(json!(1)))
+        );
+    }
+
+    #[test]
+    fn check_get_joined_strings() {
+        assert_eq!(
+            get_joined_strings("-", &json!(["a", "b", "c"])),
+            Ok(json!("a-b-c"))
+        );
+        assert_eq!(get_joined_strings("-", &json!([])), Ok(json!("")));
+        assert_eq!(
+            get_joined_strings("-", &json!(["a", 1])),
+            Err(KjqlRunnerError::InvalidStringError(json!(1)))
+        );
+        assert_eq!(
+            get_joined_strings("-", &json!("a")),
+            Err(KjqlRunnerError::InvalidArrayError(json!("a")))
+        );
+    }
+
+    #[test]
+    fn check_get_case_strings() {
+        assert_eq!(get_lowercase_string(&json!("ÀbC")), Ok(json!("àbc")));
+        assert_eq!(get_uppercase_string(&json!("àbc")), Ok(json!("ÀBC")));
+        assert_eq!(
+            get_lowercase_string(&json!(null)),
+            Err(KjqlRunnerError::InvalidStringError(json!(null)))
+        );
+        assert_eq!(
+            get_uppercase_string(&json!([])),
+            Err(KjqlRunnerError::InvalidStringError(json!([])))
+        );
+    }
+
+    #[test]
+    fn check_get_trimmed_string() {
+        assert_eq!(get_trimmed_string(&json!(" \ta b\n ")), Ok(json!("a b")));
+        assert_eq!(
+            get_trimmed_string(&json!(true)),
+            Err(KjqlRunnerError::InvalidStringError(json!(true)))
+        );
+    }
+
+    #[test]
+    fn check_get_replaced_string() {
+        assert_eq!(
+            get_replaced_string("-", "_", &json!("a-b-c")),
+            Ok(json!("a_b_c"))
+        );
+        assert_eq!(
+            get_replaced_string("a", "b", &json!({})),
+            Err(KjqlRunnerError::InvalidStringError(json!({})))
+        );
+    }
+
+    #[test]
+    fn check_get_string_range() {
+        let value = json!("héllo");
+
+        assert_eq!(
+            get_string_range(
+                &Range::new(Some(Index::new(0)), Some(Index::new(1))),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!("hé"))
+        );
+        assert_eq!(
+            get_string_range(
+                &Range::new(Some(Index::new(3)), None),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!("lo"))
+        );
+        assert_eq!(
+            get_string_range(
+                &Range::new(Some(Index::new(4)), Some(Index::new(0))),
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!("olléh"))
+        );
+        assert_eq!(
+            get_string_range(
+                &Range::new(None, None),
+                &json!(""),
+                RunnerOptions::default()
+            ),
+            Ok(json!(""))
+        );
+        assert_eq!(
+            get_string_range(
+                &Range::new(None, Some(Index::new(5))),
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::RangeOutOfBoundsError {
+                start: 0,
+                end: 5,
+                parent: value
+            })
+        );
+    }
+
+    #[test]
+    fn check_get_string_indexes() {
+        let value = json!("héllo");
+
+        assert_eq!(
+            get_string_indexes(&[Index::new(1)], &value, RunnerOptions::default()),
+            Ok(json!("é"))
+        );
+        assert_eq!(
+            get_string_indexes(
+                &[Index::new(4), Index::new(0)],
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!("oh"))
+        );
+        assert_eq!(
+            get_string_indexes(&[Index::new(5)], &value, RunnerOptions::default()),
+            Err(KjqlRunnerError::IndexOutOfBoundsError {
+                index: 5,
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_string_indexes(
+                &[Index::new(5), Index::new(0)],
+                &value,
+                RunnerOptions { lenient: true }
+            ),
+            Ok(json!("h"))
+        );
+        assert_eq!(
+            get_string_indexes(&[Index::new(0)], &json!(1), RunnerOptions::default()),
+            Err(KjqlRunnerError::InvalidStringError(json!(1)))
+        );
+    }
+
+    #[test]
+    fn check_get_string_selections() {
+        let value = json!("héllo");
+
+        assert_eq!(
+            get_string_selections(
+                &[
+                    Selection::Range(Range::new(Some(Index::new(4)), Some(Index::new(2)))),
+                    Selection::Index(Index::new(1)),
+                ],
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!("ollé"))
+        );
+        assert_eq!(
+            get_string_selections(
+                &[
+                    Selection::IndexFromEnd(Index::new(1)),
+                    Selection::IndexFromEnd(Index::new(4)),
+                ],
+                &value,
+                RunnerOptions::default()
+            ),
+            Ok(json!("oé"))
+        );
+        assert_eq!(
+            get_string_selections(
+                &[Selection::IndexFromEnd(Index::new(6))],
+                &value,
+                RunnerOptions::default()
+            ),
+            Err(KjqlRunnerError::IndexFromEndOutOfBoundsError {
+                index: 6,
+                parent: value.clone()
+            })
+        );
+        assert_eq!(
+            get_string_selections(
+                &[Selection::IndexFromEnd(Index::new(6))],
+                &value,
+                RunnerOptions { lenient: true }
+            ),
+            Ok(json!(""))
+        );
+    }
+
+    #[test]
+    fn check_get_string_range_lenient() {
+        let value = json!("héllo");
+        let options = RunnerOptions { lenient: true };
+
+        assert_eq!(
+            get_string_range(
+                &Range::new(Some(Index::new(3)), Some(Index::new(10))),
+                &value,
+                options
+            ),
+            Ok(json!("lo"))
+        );
+        assert_eq!(
+            get_string_range(
+                &Range::new(Some(Index::new(10)), Some(Index::new(20))),
+                &value,
+                options
+            ),
+            Ok(json!(""))
+        );
+    }
+
+    #[test]
+    fn check_get_to_string() {
+        assert_eq!(get_to_string(&json!("a")), json!("a"));
+        assert_eq!(get_to_string(&json!(1.5)), json!("1.5"));
+        assert_eq!(get_to_string(&json!(null)), json!("null"));
+        assert_eq!(get_to_string(&json!({ "a": [1] })), json!(r#"{"a":[1]}"#));
+    }
+
+    #[test]
+    fn check_get_to_number() {
+        assert_eq!(get_to_number(&json!(1)), Ok(json!(1)));
+        assert_eq!(get_to_number(&json!(" 42 ")), Ok(json!(42)));
+        assert_eq!(get_to_number(&json!("-1.5")), Ok(json!(-1.5)));
+        assert_eq!(
+            get_to_number(&json!("4two")),
+            Err(KjqlRunnerError::NumberConversionError(json!("4two")))
+        );
+        assert_eq!(
+            get_to_number(&json!(true)),
+            Err(KjqlRunnerError::NumberConversionError(json!(true)))
+        );
+    }
+
+    #[test]
+    fn check_get_to_json() {
+        assert_eq!(get_to_json(&json!("a")), json!(r#""a""#));
+        assert_eq!(get_to_json(&json!([1, null])), json!("[1,null]"));
+    }
+
+    #[test]
+    fn check_get_from_json() {
+        assert_eq!(
+            get_from_json(&json!(r#"{ "a": [1, 2] }"#)),
+            Ok(json!({ "a": [1, 2] }))
+        );
+        assert_eq!(get_from_json(&json!("1")), Ok(json!(1)));
+        assert_eq!(
+            get_from_json(&json!(1)),
+            Err(KjqlRunnerError::InvalidStringError(json!(1)))
+        );
+        assert_eq!(
+            get_from_json(&json!("{ nope")),
+            Err(KjqlRunnerError::FromJsonError {
+                value: json!("{ nope"),
+                reason: "key must be a string at line 1 column 3".to_string()
+            })
+        );
+    }
+}
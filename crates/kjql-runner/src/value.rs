@@ -0,0 +1,422 @@
+use std::{
+    cmp::Ordering,
+    num::NonZeroUsize,
+};
+
+use kjql_parser::tokens::{
+    BinaryOperator,
+    JsonType,
+    Order,
+    Range,
+};
+use serde_json::{
+    Number,
+    Value,
+};
+
+use crate::{
+    errors::KjqlRunnerError,
+    runner::RunnerOptions,
+};
+
+/// Returns the type of JSON `Value`.
+pub(crate) fn get_json_type(json: &Value) -> JsonType {
+    match json {
+        Value::Array(_) => JsonType::Array,
+        Value::Bool(_) => JsonType::Boolean,
+        Value::Null => JsonType::Null,
+        Value::Number(_) => JsonType::Number,
+        Value::Object(_) => JsonType::Object,
+        Value::String(_) => JsonType::String,
+    }
+}
+
+/// Internal utility for `get_descendants`.
+fn push_descendants(json: &Value, acc: &mut Vec<Value>) {
+    acc.push(json.clone());
+
+    match json {
+        Value::Array(array) => array.iter().for_each(|value| push_descendants(value, acc)),
+        Value::Object(object) => object
+            .values()
+            .for_each(|value| push_descendants(value, acc)),
+        _ => {}
+    }
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns the value followed by all its descendants, in document order, as a
+/// JSON `Value` array.
+pub(crate) fn get_descendants(json: &Value) -> Value {
+    let mut descendants = Vec::new();
+
+    push_descendants(json, &mut descendants);
+
+    Value::Array(descendants)
+}
+
+/// Returns the rank of a JSON `Value` type in the JSON type ordering.
+fn get_type_rank(json: &Value) -> u8 {
+    match json {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// Compares two JSON `Number`.
+/// Integers are compared as such to avoid any loss of precision.
+fn compare_numbers(a: &Number, b: &Number) -> Ordering {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        return a.cmp(&b);
+    }
+
+    if let (Some(a), Some(b)) = (a.as_u64(), b.as_u64()) {
+        return a.cmp(&b);
+    }
+
+    // A JSON number is always representable as a `f64`.
+    a.as_f64()
+        .unwrap()
+        .partial_cmp(&b.as_f64().unwrap())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Compares two JSON `Value` following the JSON type ordering:
+/// null < boolean < number < string < array < object.
+/// Arrays are compared element by element, objects by their sorted keys
+/// first and then by their values.
+pub(crate) fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare_values(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        (Value::Object(a), Value::Object(b)) => {
+            let mut a_keys: Vec<&String> = a.keys().collect();
+            let mut b_keys: Vec<&String> = b.keys().collect();
+
+            a_keys.sort();
+            b_keys.sort();
+
+            a_keys.cmp(&b_keys).then_with(|| {
+                a_keys
+                    .iter()
+                    .map(|key| compare_values(&a[*key], &b[*key]))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        _ => get_type_rank(a).cmp(&get_type_rank(b)),
+    }
+}
+
+/// Applies an `Order` to an `Ordering`.
+pub(crate) fn apply_order(order: Order, ordering: Ordering) -> Ordering {
+    match order {
+        Order::Ascending => ordering,
+        Order::Descending => ordering.reverse(),
+    }
+}
+
+/// Takes a reference of a `Range`, a reference of an array or an object JSON
+/// `Value`, its length and the `RunnerOptions`.
+/// Returns the boundaries of the range, `None` if nothing is selected, or an
+/// error if the range is out of bounds.
+/// In lenient mode, the boundaries are clamped to the length.
+pub(crate) fn get_range_boundaries(
+    range: &Range,
+    json: &Value,
+    len: usize,
+    options: RunnerOptions,
+) -> Result<Option<(usize, usize)>, KjqlRunnerError> {
+    let Some(non_zero_len) = NonZeroUsize::new(len) else {
+        return Ok(None);
+    };
+
+    if options.lenient {
+        return Ok(range.to_clamped_boundaries(non_zero_len));
+    }
+
+    let (start, end) = range.to_boundaries(non_zero_len);
+
+    if start + 1 > len || end + 1 > len {
+        // Out of bounds.
+        return Err(KjqlRunnerError::RangeOutOfBoundsError {
+            start,
+            end,
+            parent: json.clone(),
+        });
+    }
+
+    Ok(Some((start, end)))
+}
+
+/// Takes a reference of a JSON `Value`.
+/// Returns a reference of a JSON `Number` or an error.
+pub(crate) fn as_number(json: &Value) -> Result<&Number, KjqlRunnerError> {
+    if let Value::Number(number) = json {
+        Ok(number)
+    } else {
+        Err(KjqlRunnerError::InvalidNumberError(json.clone()))
+    }
+}
+
+/// Takes a `f64` and returns a JSON `Number` or an error if the `f64` is not
+/// finite.
+pub(crate) fn number_from_f64(float: f64) -> Result<Number, KjqlRunnerError> {
+    Number::from_f64(float).ok_or(KjqlRunnerError::NumberOverflowError)
+}
+
+/// Takes a reference of a JSON `Number` and returns it as an integer if it is
+/// one.
+pub(crate) fn as_integer(number: &Number) -> Option<i128> {
+    number
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| number.as_u64().map(i128::from))
+}
+
+/// Takes an integer and returns it as a JSON `Number` if it fits in one.
+pub(crate) fn integer_to_number(integer: i128) -> Option<Number> {
+    i64::try_from(integer)
+        .map(Number::from)
+        .or_else(|_| u64::try_from(integer).map(Number::from))
+        .ok()
+}
+
+/// Applies an arithmetic `BinaryOperator` on two JSON `Number`.
+/// Integers are kept as such unless the operation overflows or the division
+/// has a remainder, in which case the operation falls back on floats.
+pub(crate) fn compute_numbers(
+    operator: BinaryOperator,
+    a: &Number,
+    b: &Number,
+) -> Result<Number, KjqlRunnerError> {
+    let division_by_zero = || KjqlRunnerError::DivisionByZeroError(Value::Number(a.clone()));
+
+    if matches!(
+        operator,
+        BinaryOperator::Intersection | BinaryOperator::Union
+    ) {
+        return Err(KjqlRunnerError::InvalidOperandsError {
+            operator: operator.to_string(),
+            operands: Box::new((Value::Number(a.clone()), Value::Number(b.clone()))),
+        });
+    }
+
+    if let (Some(a), Some(b)) = (as_integer(a), as_integer(b)) {
+        let result = match operator {
+            BinaryOperator::Add => a.checked_add(b),
+            BinaryOperator::Subtract => a.checked_sub(b),
+            BinaryOperator::Multiply => a.checked_mul(b),
+            BinaryOperator::Divide | BinaryOperator::Remainder if b == 0 => {
+                return Err(division_by_zero());
+            }
+            BinaryOperator::Divide => (a % b == 0).then(|| a / b),
+            BinaryOperator::Remainder => Some(a % b),
+            BinaryOperator::Intersection | BinaryOperator::Union => unreachable!(),
+        };
+
+        if let Some(number) = result.and_then(integer_to_number) {
+            return Ok(number);
+        }
+    }
+
+    // A JSON number is always representable as a `f64`.
+    let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+
+    let result = match operator {
+        BinaryOperator::Add => a + b,
+        BinaryOperator::Subtract => a - b,
+        BinaryOperator::Multiply => a * b,
+        BinaryOperator::Divide | BinaryOperator::Remainder if b == 0.0 => {
+            return Err(division_by_zero());
+        }
+        BinaryOperator::Divide => a / b,
+        BinaryOperator::Remainder => a % b,
+        BinaryOperator::Intersection | BinaryOperator::Union => unreachable!(),
+    };
+
+    number_from_f64(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use kjql_parser::tokens::{
+        BinaryOperator,
+        Order,
+    };
+    use serde_json::{
+        Number,
+        json,
+    };
+
+    use super::{
+        apply_order,
+        as_number,
+        compare_values,
+        compute_numbers,
+        get_descendants,
+    };
+    use crate::errors::KjqlRunnerError;
+
+    #[test]
+    fn check_get_descendants() {
+        assert_eq!(
+            get_descendants(&json!({ "a": [1, { "b": 2 }], "c": null })),
+            json!([
+                { "a": [1, { "b": 2 }], "c": null },
+                [1, { "b": 2 }],
+                1,
+                { "b": 2 },
+                2,
+                null
+            ])
+        );
+        assert_eq!(get_descendants(&json!("a")), json!(["a"]));
+    }
+
+    #[test]
+    fn check_compare_values() {
+        let ordered = [
+            json!(null),
+            json!(false),
+            json!(true),
+            json!(-1),
+            json!(0.5),
+            json!(1),
+            json!(u64::MAX),
+            json!(""),
+            json!("a"),
+            json!("b"),
+            json!([]),
+            json!([1]),
+            json!([1, 2]),
+            json!([2]),
+            json!({}),
+            json!({ "a": 1 }),
+            json!({ "a": 2 }),
+            json!({ "b": 1 }),
+        ];
+
+        for window in ordered.windows(2) {
+            assert_eq!(
+                compare_values(&window[0], &window[1]),
+                Ordering::Less,
+                "{} < {}",
+                window[0],
+                window[1]
+            );
+            assert_eq!(compare_values(&window[1], &window[0]), Ordering::Greater);
+        }
+
+        assert_eq!(compare_values(&json!(1), &json!(1.0)), Ordering::Equal);
+        assert_eq!(
+            compare_values(&json!({ "a": 1, "b": 2 }), &json!({ "b": 2, "a": 1 })),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn check_apply_order() {
+        assert_eq!(
+            apply_order(Order::Ascending, Ordering::Less),
+            Ordering::Less
+        );
+        assert_eq!(
+            apply_order(Order::Descending, Ordering::Less),
+            Ordering::Greater
+        );
+        assert_eq!(
+            apply_order(Order::Descending, Ordering::Equal),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn check_as_number() {
+        assert_eq!(as_number(&json!(1)), Ok(&Number::from(1)));
+        assert_eq!(
+            as_number(&json!("1")),
+            Err(KjqlRunnerError::InvalidNumberError(json!("1")))
+        );
+    }
+
+    #[test]
+    fn check_compute_numbers() {
+        let integer = |integer: i64| Number::from(integer);
+        let float = |float: f64| Number::from_f64(float).unwrap();
+
+        assert_eq!(
+            compute_numbers(BinaryOperator::Add, &integer(1), &integer(-3)),
+            Ok(integer(-2))
+        );
+        assert_eq!(
+            compute_numbers(
+                BinaryOperator::Add,
+                &Number::from(u64::MAX - 1),
+                &integer(1)
+            ),
+            Ok(Number::from(u64::MAX))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Add, &integer(1), &float(0.5)),
+            Ok(float(1.5))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Add, &Number::from(u64::MAX), &integer(1)),
+            Ok(float(u64::MAX as f64 + 1.0))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Subtract, &integer(1), &integer(3)),
+            Ok(integer(-2))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Multiply, &integer(6), &integer(7)),
+            Ok(integer(42))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Multiply, &float(1.5), &integer(2)),
+            Ok(float(3.0))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Divide, &integer(2048), &integer(1024)),
+            Ok(integer(2))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Divide, &integer(1536), &integer(1024)),
+            Ok(float(1.5))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Remainder, &integer(-7), &integer(3)),
+            Ok(integer(-1))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Remainder, &float(7.5), &integer(2)),
+            Ok(float(1.5))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Divide, &integer(1), &integer(0)),
+            Err(KjqlRunnerError::DivisionByZeroError(json!(1)))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Remainder, &float(1.5), &float(0.0)),
+            Err(KjqlRunnerError::DivisionByZeroError(json!(1.5)))
+        );
+        assert_eq!(
+            compute_numbers(BinaryOperator::Add, &float(f64::MAX), &float(f64::MAX)),
+            Err(KjqlRunnerError::NumberOverflowError)
+        );
+    }
+}
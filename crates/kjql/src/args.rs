@@ -14,6 +14,11 @@ The following tokens are available to build up a query:
 
 == Separators ==
 
+Comments # line /* block */
+    ┬
+    ╰→ ignored like whitespaces, useful to annotate queries read with --query,
+       they are not part of quoted keys and are rejected by --fmt
+
 Group separator ,
     ┬
     ╰→ query '"a","b","c"' will build up an array from sub-queries
@@ -24,18 +29,29 @@ Group separator ,
 
 Array index selector [0,2,1]
     ┬
-    ╰→ indexes can be used in arbitrary order
+    ╰→ indexes can be used in arbitrary order,
+       mixed with ranges [0,3:6,9] to concatenate the selections,
+       counted from the end when negative in such lists [0,3:6,-1],
+       missing indexes are skipped with --lenient,
+       also selects characters when used on a string
 
 Array range selector [2:0]
     ┬
     ╰→ range can be in natural order [0:2], reversed [2:0],
-       without lower [:2] or upper bound [0:]
+       without lower [:2] or upper bound [0:],
+       also selects a substring when used on a string,
+       bounds are clamped to the length with --lenient
 
 
-Lens selector |={"a","b"=true,"c"=null,"d"=1,"e"="string"}
+Lens selector |={"a","b"=true,"c"=null,"d"=1,"e"="string","f"=<array>}
     ┬
     ╰→ lens can be a combination of one or more selectors with an optional value,
-       a value being any of boolean | null | number | string
+       a value being any of boolean | null | number | string | <type> | exists
+
+Type selector <string>
+    ┬
+    ╰→ keeps the elements of an array of the given type,
+       a type being any of array | boolean | null | number | object | string
 
 -- Objects --
 
@@ -49,19 +65,96 @@ Multi key selector {"a","c","b"}
 
 Object index selector {0,2,1}
     ┬
-    ╰→ indexes can be used in arbitrary order
+    ╰→ indexes can be used in arbitrary order,
+       mixed with ranges {0,2:4} to concatenate the selections,
+       counted from the end when negative in such lists {0,-1},
+       missing indexes are skipped with --lenient
+
+Object key range selector {"b":"f"}
+    ┬
+    ╰→ selects the entries from one key to another in document order,
+       the end key is included with : and excluded with .. {"b".."f"},
+       reversed when the end key comes first
 
 Object range selector {2:0}
     ┬
     ╰→ range can be in natural order {0:2}, reversed {2:0},
-       without lower {:2} or upper bound {0:}
+       without lower {:2} or upper bound {0:},
+       bounds are clamped to the length with --lenient
 
 == Operators ==
 
+Arithmetic operators + - * / %
+    ┬
+    ╰→ query '"price" * "qty" + 1' computes numbers out of sub-queries
+       and number literals, which are only valid as operands,
+       * / % take precedence over + -,
+       integers are kept as such when the result is an integer
+
+Average operator avg
+    ┬
+    ╰→ average of an array of numbers
+
+Conditional operator if "a"=1,"b" then "c" else "d" end
+    ┬
+    ╰→ runs the then query if any of the lens predicates matches,
+       the else query otherwise, predicates follow the lens selector syntax
+
+Descendants operator descendants
+    ┬
+    ╰→ array of the value followed by all its nested values in document order
+
+First and last operators first last
+    ┬
+    ╰→ first or last element of an array, null for an empty array
+
 Flatten operator ..
     ┬
     ╰→ flattens arrays and objects
 
+From JSON operator fromjson
+    ┬
+    ╰→ parses a string as JSON so that selectors can continue into it
+
+Group by operator group_by("a""b")
+    ┬
+    ╰→ groups the elements of an array into an object of arrays
+       keyed by the stringified sub-query result
+
+Has operator has("a") has(0)
+    ┬
+    ╰→ returns true if the object has the key or the array has the index,
+       false otherwise
+
+Join operator join(",")
+    ┬
+    ╰→ joins an array of strings with a separator
+
+Limit operator limit(10)
+    ┬
+    ╰→ keeps at most the given number of elements of an array,
+       with --stream-window, leading limit, skip and first operators
+       apply to the stream of JSON documents and stop reading it early
+
+Lower and upper operators lower upper
+    ┬
+    ╰→ converts a string to lowercase or uppercase
+
+Merge operator merge("a","b""c") merge_concat("a","b")
+    ┬
+    ╰→ deep merges the objects of two or more sub-queries,
+       the right values taking precedence, arrays are replaced
+       with merge and concatenated with merge_concat
+
+Min and max operators min max
+    ┬
+    ╰→ minimum and maximum of an array of numbers
+
+Min by and max by operators min_by("a""b") max_by("a""b")
+    ┬
+    ╰→ first element of an array with the minimum or maximum
+       numeric sub-query result
+
 Pipe in operator |>
     ┬
     ╰→ applies the next tokens in parallel on each element of an array
@@ -70,10 +163,121 @@ Pipe out operator <|
     ┬
     ╰→ stops the parallelization initiated by the pipe in operator
 
+Replace operator replace("a","b")
+    ┬
+    ╰→ replaces all the occurrences of a pattern in a string
+
+Set operators - & |
+    ┬
+    ╰→ query '"a" - "b"' computes the difference, intersection or union
+       of arrays with distinct values in a stable order,
+       objects are combined by key with the right values taking precedence,
+       | binds looser than & which binds looser than the arithmetic operators
+
+Skip operator skip(10)
+    ┬
+    ╰→ drops at most the given number of elements of an array
+
+Sort operator sort
+    ┬
+    ╰→ stable sort of an array following the JSON type ordering
+       null < boolean < number < string < array < object,
+       sort_desc sorts in descending order
+
+Sort by operator sort_by("a""b")
+    ┬
+    ╰→ stable sort of an array based on a sub-query applied on each element,
+       sort_by_desc("a""b") sorts in descending order
+
+Split operator split(",")
+    ┬
+    ╰→ splits a string into an array of strings,
+       an empty separator splits it into characters
+
+Sum operator sum
+    ┬
+    ╰→ sum of an array of numbers, 0 for an empty array
+
+To JSON operator tojson
+    ┬
+    ╰→ serializes any value to a JSON string
+
+To number operator tonumber
+    ┬
+    ╰→ converts a string to a number, numbers are kept as is
+
+To string operator tostring
+    ┬
+    ╰→ serializes any value to a string, strings are kept as is
+
+Transpose operator transpose transpose_pad
+    ┬
+    ╰→ transposes an array of arrays, arrays of different lengths
+       are an error with transpose and padded with null with transpose_pad
+
+Trim operator trim
+    ┬
+    ╰→ removes the leading and trailing whitespaces of a string
+
 Truncate operator !
     ┬
     ╰→ maps the output into simple JSON primitives
        boolean | null | number | string | [] | {}
+
+Type operator type
+    ┬
+    ╰→ returns the JSON type name of the value
+
+Unique operator unique
+    ┬
+    ╰→ removes the duplicated values of an array,
+       keeping the first occurrences in the original order
+
+Unique by operator unique_by("a""b")
+    ┬
+    ╰→ removes the elements of an array sharing the same sub-query result,
+       keeping the first occurrences in the original order
+
+Values operator values
+    ┬
+    ╰→ array of the values of an object, arrays are kept as is
+
+Zip operator zip("a","b") zip_pad("a","b")
+    ┬
+    ╰→ combines the arrays of two or more sub-queries into an array of tuples,
+       arrays of different lengths are an error with zip
+       and padded with null with zip_pad
+
+Zip object operator zip_object("a","b") zip_object_pad("a","b")
+    ┬
+    ╰→ same as zip but builds objects keyed by the last key of each sub-query
+
+== Dotted paths ==
+
+Dotted path devDependencies.react keywords.3 a.b[0:2]
+    ┬
+    ╰→ unquoted keys separated by dots, square brackets for indexes, numbers
+       being indexes on arrays and keys on objects (responses.200), used when a query made of these characters only isn't valid otherwise
+       or forced with --dotted, operator names are keys in dotted paths
+       (items.first) but a single operator name is the operator (sum)
+
+== JSONPath ==
+
+JSONPath $.spec.containers[?(@.name == 'app')].image
+    ┬
+    ╰→ used when the query starts with $, supports .key ['key'] [0] [1:3]
+       * .. and filters made of @ paths, == and ||,
+       multiple selected values are returned as an array, missing values
+       are skipped or return null for a single selected value
+
+== JSON Pointers ==
+
+JSON Pointer /spec/containers/0/image
+    ┬
+    ╰→ used when the query starts with /, ~0 and ~1 being unescaped as ~ and /,
+       numeric reference tokens are indexes on arrays and keys on objects,
+       --pointers writes the JSON Pointers of the selected values instead,
+       with selectors, first, last, limit, skip and values only
 "#;
 
 #[allow(clippy::struct_excessive_bools)]
@@ -104,6 +308,31 @@ pub(crate) struct Args {
     )]
     pub(crate) json_file: Option<PathBuf>,
 
+    /// Format check flag.
+    #[arg(
+        help = "Check that the query is formatted instead of writing it",
+        long = "check",
+        requires = "fmt",
+        short = 'c'
+    )]
+    pub(crate) check: bool,
+
+    /// Dotted path flag.
+    #[arg(
+        help = "Use the dotted path syntax for the query (e.g. a.b[0].c)",
+        long = "dotted",
+        short = 'd'
+    )]
+    pub(crate) dotted: bool,
+
+    /// Format flag.
+    #[arg(
+        conflicts_with_all = ["json_file", "pointers", "stream", "validate"],
+        help = "Format the query in a canonical layout instead of running it",
+        long = "fmt"
+    )]
+    pub(crate) fmt: bool,
+
     /// Inline JSON flag.
     #[arg(
         conflicts_with = "validate",
@@ -113,6 +342,14 @@ pub(crate) struct Args {
     )]
     pub(crate) inline: bool,
 
+    /// Lenient flag.
+    #[arg(
+        help = "Clamp out of bounds ranges and skip missing indexes",
+        long = "lenient",
+        short = 'l'
+    )]
+    pub(crate) lenient: bool,
+
     /// Query from file flag.
     #[arg(
         group = "no-query",
@@ -124,6 +361,15 @@ pub(crate) struct Args {
     )]
     pub(crate) query_from_file: Option<PathBuf>,
 
+    /// Pointers flag.
+    #[arg(
+        conflicts_with = "validate",
+        help = "Write the JSON Pointers of the selected values instead of the values",
+        long = "pointers",
+        short = 'p'
+    )]
+    pub(crate) pointers: bool,
+
     /// Raw string flag.
     #[arg(
         help = "Write to stdout without JSON double-quotes (string only)",
@@ -140,6 +386,15 @@ pub(crate) struct Args {
     )]
     pub(crate) stream: bool,
 
+    /// Stream window flag.
+    #[arg(
+        help = "Apply leading limit, skip and first operators to the stream of JSON documents",
+        long = "stream-window",
+        requires = "stream",
+        short = 'w'
+    )]
+    pub(crate) stream_window: bool,
+
     /// Validate JSON data flag.
     #[arg(
         group = "no-query",
@@ -3,6 +3,7 @@ mod args;
 mod panic;
 
 use std::{
+    borrow::Cow,
     path::Path,
     process::exit,
 };
@@ -19,7 +20,26 @@ use colored_json::{
     CompactFormatter,
     PrettyFormatter,
 };
-use kjql_runner::runner;
+use kjql_parser::{
+    dotted::parse_dotted,
+    errors::KjqlParserError,
+    jsonpath::parse_jsonpath,
+    parser::parse,
+    pointer::{
+        parse_pointer,
+        pointer_to_tokens,
+    },
+    serializer::format,
+    tokens::Token,
+};
+use kjql_runner::{
+    errors::KjqlRunnerError,
+    pointer::get_pointers,
+    runner::{
+        self,
+        RunnerOptions,
+    },
+};
 use panic::use_custom_panic_hook;
 use serde::Deserialize;
 use serde_json::Value;
@@ -62,7 +82,191 @@ fn render(result: Result<String>) {
     }
 }
 
-async fn process_json(json: &str, args: &Args) -> Result<String> {
+/// Reads the query from the arguments or from the query file.
+async fn read_query(args: &Args) -> Result<String> {
+    match args.query_from_file.as_deref() {
+        Some(path) => read_file(path).await,
+        None => Ok(args.query.as_deref().unwrap_or_default().to_string()),
+    }
+}
+
+/// Returns `true` if the query contains a `#` line comment or a `/* */`
+/// block comment outside of its quoted keys.
+fn has_comments(query: &str) -> bool {
+    let mut quoted = false;
+    let mut characters = query.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return true,
+            '/' if !quoted && characters.peek() == Some(&'*') => return true,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Formats the query with `--fmt`.
+/// With `--check`, fails if the query is not already formatted.
+/// Only queries of the regular syntax are formatted, queries with comments are
+/// rejected since formatting would drop them.
+async fn format_query(args: &Args) -> Result<String> {
+    let query = read_query(args).await?;
+
+    if has_comments(&query) {
+        return Err(anyhow!(
+            "Queries with comments can't be formatted, the comments would be dropped"
+        ));
+    }
+
+    let tokens = parse(query.trim()).map_err(|error| query_error(query.trim(), error))?;
+    let formatted = format(&tokens).map_err(KjqlRunnerError::from)?;
+
+    if !args.check {
+        return Ok(formatted);
+    }
+
+    // A single trailing newline is expected at the end of query files.
+    if query.strip_suffix('\n').unwrap_or(&query) == formatted {
+        Ok("Query is formatted".to_string())
+    } else {
+        Err(anyhow!("Query is not formatted, expected:\n{formatted}"))
+    }
+}
+
+/// Renders the line of the query containing the byte `offset` along with a
+/// caret under the offending character.
+fn render_caret(query: &str, offset: usize) -> String {
+    let offset = offset.min(query.len());
+    let start = query[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let end = query[offset..]
+        .find('\n')
+        .map_or(query.len(), |index| offset + index);
+    // Keep the tabs so that the caret stays aligned with the query.
+    let padding: String = query[start..offset]
+        .chars()
+        .map(|character| if character == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    format!("{}\n{padding}^", &query[start..end])
+}
+
+/// Converts a parser error of the query into an error, rendering a caret under
+/// the offending position for parsing errors.
+fn query_error(query: &str, error: KjqlParserError) -> anyhow::Error {
+    if let KjqlParserError::ParsingError { offset, .. } = error {
+        let caret = render_caret(query, offset);
+
+        return anyhow!("{}\n{caret}", KjqlRunnerError::from(error));
+    }
+
+    KjqlRunnerError::from(error).into()
+}
+
+/// Returns whether the query only contains characters of the dotted path
+/// syntax, i.e. unquoted keys, numbers, dots and square brackets.
+/// Any other character (quotes, operators, comments...) belongs to the regular
+/// syntax.
+fn is_dotted_path(query: &str) -> bool {
+    let query = query.trim();
+
+    query.chars().next().is_some_and(|first| {
+        first.is_alphanumeric() || first == '_' || first == '$' || first == '.'
+    }) && query.chars().all(|character| {
+        character.is_alphanumeric()
+            || character.is_whitespace()
+            || matches!(character, '_' | '$' | '-' | '.' | '[' | ']' | ':' | ',')
+    })
+}
+
+/// Parses the query into tokens.
+/// The dotted path syntax is used when `dotted` is set or as a fallback when
+/// the query doesn't parse with the regular syntax and only contains dotted
+/// path characters, in which case the dotted path error is reported.
+/// A query starting with `$` is parsed as a JSONPath.
+///
+/// Note that a query valid in both syntaxes uses the regular one, e.g. `sum`
+/// is the sum operator while `items.first` selects the `first` key.
+fn parse_query(query: &str, dotted: bool) -> Result<Vec<Token<'_>>> {
+    if query.is_empty() {
+        return Err(KjqlRunnerError::EmptyQueryError.into());
+    }
+
+    if dotted {
+        return parse_dotted(query).map_err(|error| query_error(query, error));
+    }
+
+    let Some(path) = query.trim().strip_prefix('$') else {
+        return match parse(query) {
+            Err(_) if is_dotted_path(query) => parse_dotted(query),
+            tokens => tokens,
+        }
+        .map_err(|error| query_error(query, error));
+    };
+
+    // Only keys like `$schema` fall back to dotted paths, `$` followed by a
+    // dot or a bracket being the JSONPath root.
+    match parse_jsonpath(query) {
+        Err(_) if !path.is_empty() && !path.starts_with(['.', '[']) && is_dotted_path(query) => {
+            parse_dotted(query)
+        }
+        tokens => tokens,
+    }
+    .map_err(|error| query_error(query, error))
+}
+
+/// Parses the query as a JSON Pointer if it starts with a slash.
+/// Returns the unescaped reference tokens of the pointer, if any.
+/// A query starting with a block comment is not a JSON Pointer, as long as it
+/// parses as a regular query, `/*/a` being a valid pointer.
+/// Surrounding whitespaces are ignored, like for the other queries.
+fn parse_pointer_query(query: &str) -> Result<Option<Vec<Cow<'_, str>>>> {
+    let query = query.trim();
+
+    if !query.starts_with('/') || (query.starts_with("/*") && parse(query).is_ok()) {
+        return Ok(None);
+    }
+
+    Ok(Some(parse_pointer(query).map_err(KjqlRunnerError::from)?))
+}
+
+/// Splits the leading `skip`, `limit` and `first` operators off the tokens so
+/// that they apply to the stream of JSON documents instead of each document,
+/// with `--stream-window`.
+/// Returns the number of documents to skip, the optional index of the
+/// document to stop at and the remaining tokens.
+fn get_stream_window<'a>(tokens: &'a [Token<'a>]) -> (usize, Option<usize>, &'a [Token<'a>]) {
+    // Leading operators are ambiguous along with groups, keep them as is.
+    if tokens.contains(&Token::GroupSeparator) {
+        return (0, None, tokens);
+    }
+
+    let mut start = 0;
+    let mut end: Option<usize> = None;
+    let mut consumed = 0;
+
+    for token in tokens {
+        let limit = match token {
+            Token::SkipOperator(count) => {
+                start = end.map_or(start + count, |end| end.min(start + count));
+                consumed += 1;
+                continue;
+            }
+            Token::LimitOperator(count) => *count,
+            Token::FirstOperator => 1,
+            _ => break,
+        };
+
+        end = Some(end.map_or(start + limit, |end| end.min(start + limit)));
+        consumed += 1;
+    }
+
+    (start, end, &tokens[consumed..])
+}
+
+async fn process_json(json: &str, tokens: &[Token<'_>], args: &Args) -> Result<String> {
     if args.validate {
         return serde_json::from_str::<Value>(json).map_or_else(
             |_| Err(anyhow!("Invalid JSON file or content")),
@@ -70,17 +274,21 @@ async fn process_json(json: &str, args: &Args) -> Result<String> {
         );
     }
 
-    let query = match args.query_from_file.as_deref() {
-        Some(path) => read_file(path).await?,
-        None => args.query.as_deref().unwrap().to_string(),
-    };
-
     let mut deserializer = serde_json::Deserializer::from_str(json);
     deserializer.disable_recursion_limit();
     let deserializer = Deserializer::new(&mut deserializer);
     let value: Value = Value::deserialize(deserializer)
         .with_context(|| "Failed to deserialize the JSON data".to_string())?;
-    let result: Value = runner::raw(&query, &value)?;
+    let options = RunnerOptions {
+        lenient: args.lenient,
+    };
+    let result: Value = if args.pointers {
+        get_pointers(tokens, &value, options)?
+    } else if tokens.is_empty() {
+        value
+    } else {
+        runner::token_with_options(tokens, &value, options)?
+    };
     if args.inline {
         return ColoredFormatter::new(CompactFormatter {})
             .to_colored_json_auto(&result)
@@ -100,9 +308,44 @@ async fn process_json(json: &str, args: &Args) -> Result<String> {
 async fn main() -> Result<()> {
     use_custom_panic_hook();
     let args = Args::parse();
+
+    if args.fmt {
+        render(format_query(&args).await);
+
+        return Ok(());
+    }
+
+    let query = if args.validate {
+        String::new()
+    } else {
+        read_query(&args).await?
+    };
+    let references = match parse_pointer_query(&query) {
+        Ok(references) => references,
+        Err(error) => {
+            render(Err(error));
+
+            return Ok(());
+        }
+    };
+    let tokens = if args.validate {
+        vec![]
+    } else if let Some(references) = references.as_deref() {
+        pointer_to_tokens(references)
+    } else {
+        match parse_query(&query, args.dotted) {
+            Ok(tokens) => tokens,
+            Err(error) => {
+                render(Err(error));
+
+                return Ok(());
+            }
+        }
+    };
+
     if let Some(path) = args.json_file.as_deref() {
         let conetents = read_file(path).await?;
-        render(process_json(&conetents, &args).await);
+        render(process_json(&conetents, &tokens, &args).await);
 
         return Ok(());
     }
@@ -110,13 +353,30 @@ async fn main() -> Result<()> {
     let mut stdout = stdout();
 
     if args.stream {
+        let (start, end, tokens) = if args.stream_window {
+            get_stream_window(&tokens)
+        } else {
+            (0, None, &tokens[..])
+        };
         let mut reader = BufReader::new(stdin()).lines();
-        while let Some(mut line) = reader
-            .next_line()
-            .await
-            .with_context(|| "Failed to read stream".to_string())?
-        {
-            render(process_json(&line, &args).await);
+        let mut index = 0;
+
+        // Stop reading the stream as soon as the window is exhausted.
+        while end.is_none_or(|end| index < end) {
+            let Some(mut line) = reader
+                .next_line()
+                .await
+                .with_context(|| "Failed to read stream".to_string())?
+            else {
+                break;
+            };
+
+            index += 1;
+            if index <= start {
+                continue;
+            }
+
+            render(process_json(&line, tokens, &args).await);
 
             stdout
                 .flush()
@@ -138,7 +398,194 @@ async fn main() -> Result<()> {
         .with_context(|| "Failed to read piped content from stdin".to_string())?;
     let lines = String::from_utf8(buffer)
         .with_context(|| "Failed to convert piped content from stdin".to_string())?;
-    render(process_json(&lines, &args).await);
+    render(process_json(&lines, &tokens, &args).await);
 
     Ok(())
 }
+
+#[test]
+fn check_get_stream_window() {
+    let tokens = [
+        Token::SkipOperator(2),
+        Token::LimitOperator(3),
+        Token::KeySelector("a"),
+    ];
+    assert_eq!(get_stream_window(&tokens), (2, Some(5), &tokens[2..]));
+
+    let tokens = [
+        Token::LimitOperator(3),
+        Token::SkipOperator(1),
+        Token::FirstOperator,
+    ];
+    assert_eq!(get_stream_window(&tokens), (1, Some(2), &tokens[3..]));
+
+    let tokens = [Token::FirstOperator, Token::SkipOperator(1)];
+    assert_eq!(get_stream_window(&tokens), (1, Some(1), &tokens[2..]));
+
+    let tokens = [Token::KeySelector("a"), Token::FirstOperator];
+    assert_eq!(get_stream_window(&tokens), (0, None, &tokens[..]));
+
+    let tokens = [
+        Token::FirstOperator,
+        Token::GroupSeparator,
+        Token::LastOperator,
+    ];
+    assert_eq!(get_stream_window(&tokens), (0, None, &tokens[..]));
+}
+
+#[test]
+fn check_parse_query() {
+    assert_eq!(
+        parse_query("a.b", false).unwrap(),
+        vec![Token::KeySelector("a"), Token::KeySelector("b")]
+    );
+    assert_eq!(
+        parse_query(r#""a""b""#, false).unwrap(),
+        vec![Token::KeySelector("a"), Token::KeySelector("b")]
+    );
+    assert_eq!(parse_query("sum", false).unwrap(), vec![Token::SumOperator]);
+    // A bare number is a dotted path, number literals being operands only.
+    assert_eq!(
+        parse_query("5", false).unwrap(),
+        vec![Token::ReferenceSelector("5")]
+    );
+    assert!(parse_query(r#""a",5"#, false).is_err());
+    assert_eq!(
+        parse_query("sum", true).unwrap(),
+        vec![Token::KeySelector("sum")]
+    );
+    assert_eq!(
+        parse_query("$.a['b']", false).unwrap(),
+        parse_jsonpath("$.a['b']").unwrap()
+    );
+    assert_eq!(
+        parse_query("$schema.a", false).unwrap(),
+        vec![Token::KeySelector("$schema"), Token::KeySelector("a")]
+    );
+    assert!(parse_query("$.a[-1]", false).is_err());
+    assert!(parse_query("$[-1]", false).is_err());
+    assert_eq!(
+        parse_query("a[-1]", false).unwrap(),
+        parse_query(r#""a"[-1]"#, false).unwrap()
+    );
+    assert_eq!(
+        parse_query("items.first", false).unwrap(),
+        vec![Token::KeySelector("items"), Token::KeySelector("first")]
+    );
+    assert_eq!(
+        parse_query("a..b", false).unwrap_err().to_string(),
+        "Unable to parse input ..b after KeySelector \"a at offset 2, expected an identifier or a number\na..b\n  ^"
+    );
+    assert!(parse_query("", true).is_err());
+    assert_eq!(
+        parse_query(r#""a"[0,"#, false).unwrap_err().to_string(),
+        "Unable to parse input [0, after KeySelector \"a at offset 6, expected a number, `:` or `-`\n\"a\"[0,\n      ^"
+    );
+}
+
+#[test]
+fn check_is_dotted_path() {
+    assert!(is_dotted_path("devDependencies.react"));
+    assert!(is_dotted_path(" keywords[0:2]\n"));
+    assert!(is_dotted_path(".a.3"));
+    assert!(!is_dotted_path(r#""a".b"#));
+    assert!(!is_dotted_path("a|b"));
+    assert!(!is_dotted_path("[0].a"));
+    assert!(!is_dotted_path(""));
+}
+
+#[test]
+fn check_render_caret() {
+    assert_eq!(render_caret(r#""a" "b"#, 4), "\"a\" \"b\n    ^");
+    assert_eq!(render_caret("\"a\"\n\t[0 x]", 8), "\t[0 x]\n\t   ^");
+    assert_eq!(render_caret("\"é\" x", 5), "\"é\" x\n    ^");
+    assert_eq!(render_caret("[0", 2), "[0\n  ^");
+}
+
+#[test]
+fn check_parse_pointer_query() {
+    assert_eq!(parse_pointer_query(r#""a""#).unwrap(), None);
+    assert_eq!(
+        parse_pointer_query("/a/0").unwrap(),
+        Some(vec![Cow::Borrowed("a"), Cow::Borrowed("0")])
+    );
+    assert!(parse_pointer_query("/a~").is_err());
+    assert_eq!(
+        parse_pointer_query("/a/b\n").unwrap(),
+        Some(vec![Cow::Borrowed("a"), Cow::Borrowed("b")])
+    );
+    assert_eq!(
+        parse_pointer_query(" /a/b").unwrap(),
+        Some(vec![Cow::Borrowed("a"), Cow::Borrowed("b")])
+    );
+    assert_eq!(parse_pointer_query(r#"/* a */ "b""#).unwrap(), None);
+    assert_eq!(
+        parse_pointer_query("/*/a").unwrap(),
+        Some(vec![Cow::Borrowed("*"), Cow::Borrowed("a")])
+    );
+    assert!(parse_pointer_query("/*/a~").is_err());
+}
+
+#[tokio::test]
+async fn check_format_query() {
+    let format_args = |query: &str, check| {
+        let flags: &[&str] = if check {
+            &["--fmt", "--check"]
+        } else {
+            &["--fmt"]
+        };
+
+        Args::parse_from(["kjql"].iter().chain(flags).chain([&query]))
+    };
+
+    assert_eq!(
+        format_query(&format_args(r#""a"  "b"|={"c","d"},"e""#, false))
+            .await
+            .unwrap(),
+        "\"a\"\"b\" |={\n  \"c\",\n  \"d\"\n},\n\"e\""
+    );
+    assert!(
+        format_query(&format_args(r#""a" "b""#, true))
+            .await
+            .is_err()
+    );
+    assert!(
+        format_query(&format_args("\"a\"\"b\"\n", true))
+            .await
+            .is_ok()
+    );
+    assert!(
+        format_query(&format_args("# pick a\n\"a\" /* x */ \"b\"\n", false))
+            .await
+            .is_err()
+    );
+    assert!(
+        format_query(&format_args("\"a\"\"b\" # b\n", true))
+            .await
+            .is_err()
+    );
+    assert!(
+        format_query(&format_args("items.first", false))
+            .await
+            .is_err()
+    );
+    assert!(format_query(&format_args("$.a", false)).await.is_err());
+}
+
+#[test]
+fn check_fmt_args() {
+    let args = Args::parse_from(["kjql", "fmt", "data.json"]);
+
+    assert!(!args.fmt);
+    assert_eq!(args.query.as_deref(), Some("fmt"));
+    assert!(Args::try_parse_from(["kjql", "--fmt", r#""a""#, "data.json"]).is_err());
+    assert!(Args::try_parse_from(["kjql", "--check", r#""a""#]).is_err());
+}
+
+#[test]
+fn check_has_comments() {
+    assert!(has_comments("# pick a\n\"a\""));
+    assert!(has_comments(r#""a" /* x */ "b""#));
+    assert!(!has_comments(r#""a#b""/*""#));
+    assert!(!has_comments(r#""a" / "b""#));
+}